@@ -10,4 +10,142 @@ pub enum EscrowError {
   InvalidMintA,
   #[msg("Invalid mint b")]
   InvalidMintB,
+  #[msg("Escrow is not listed in the maker registry")]
+  EscrowNotRegistered,
+  #[msg("Remaining accounts do not match the maker registry")]
+  RegistryMismatch,
+  #[msg("Taker is not on the escrow allowlist")]
+  TakerNotAllowed,
+  #[msg("Allowlist proof is too long")]
+  ProofTooLong,
+  #[msg("Escrow is token-gated and no gate account was provided")]
+  GateAccountMissing,
+  #[msg("Gate account holds the wrong mint")]
+  InvalidGateMint,
+  #[msg("Gate account is not owned by the taker")]
+  InvalidGateOwner,
+  #[msg("Gate account balance is below the required minimum")]
+  InsufficientGateBalance,
+  #[msg("Escrow is oracle-priced and no price feed was provided")]
+  PriceFeedMissing,
+  #[msg("Price feed is not the pinned feed or is not a trading price account")]
+  InvalidPriceFeed,
+  #[msg("Oracle price is stale")]
+  StalePrice,
+  #[msg("Oracle price is not positive")]
+  NegativePrice,
+  #[msg("Oracle confidence interval is too wide")]
+  PriceConfidenceTooWide,
+  #[msg("Math overflow")]
+  MathOverflow,
+  #[msg("Escrow has a conditional order that has not triggered yet")]
+  TriggerPending,
+  #[msg("Oracle price has not reached the trigger")]
+  TriggerNotReached,
+  #[msg("Escrow restricts its takers or pricing")]
+  EscrowRestricted,
+  #[msg("Escrows do not cross")]
+  OrdersDoNotCross,
+  #[msg("Callback program cannot be the escrow program")]
+  InvalidCallbackProgram,
+  #[msg("Callback did not pay the maker in full")]
+  FlashRepaymentMissing,
+  #[msg("Vesting cliff must fall within a non-zero duration")]
+  InvalidVestingSchedule,
+  #[msg("Vesting accounts must be passed exactly when the escrow vests")]
+  VestingAccountMismatch,
+  #[msg("Nothing has unlocked since the last claim")]
+  NothingToClaim,
+  #[msg("Tranche size, interval and price must be non-zero")]
+  InvalidTrancheSchedule,
+  #[msg("Escrow fills in tranches through take_tranche")]
+  TranchedEscrow,
+  #[msg("Escrow is not split into tranches")]
+  NotTranched,
+  #[msg("Next tranche is not available yet")]
+  TrancheNotReady,
+  #[msg("Tranches cannot change once one has been filled")]
+  TranchesStarted,
+  #[msg("Maker does not accept reservations")]
+  ReservationsDisabled,
+  #[msg("Escrow is reserved")]
+  EscrowReserved,
+  #[msg("Reservation account is required for this escrow")]
+  ReservationMissing,
+  #[msg("Reservation window has not lapsed")]
+  ReservationActive,
+  #[msg("RFQ is not open")]
+  RfqNotOpen,
+  #[msg("Quote does not match the RFQ's mints or size")]
+  QuoteDoesNotMatchRfq,
+  #[msg("RFQ already holds the maximum number of quotes")]
+  TooManyQuotes,
+  #[msg("Maker already quoted this RFQ")]
+  DuplicateQuote,
+  #[msg("Escrow is quoted into an RFQ")]
+  EscrowQuoted,
+  #[msg("Quote is not linked to this RFQ")]
+  QuoteNotFound,
+  #[msg("Approvers must be distinct and the threshold between one and their count")]
+  InvalidApprovers,
+  #[msg("Signer is not an approver of this multisig")]
+  NotAnApprover,
+  #[msg("Approver already approved this proposal")]
+  AlreadyApproved,
+  #[msg("Proposal has not reached the approval threshold")]
+  ThresholdNotReached,
+  #[msg("Accounts do not match the proposed action")]
+  ProposalMismatch,
+  #[msg("Instructions sysvar is required for a solo take")]
+  InstructionsSysvarMissing,
+  #[msg("Solo take shares its transaction with another program")]
+  TakeNotAlone,
+  #[msg("Solo take cannot be called through CPI")]
+  TakeViaCpi,
+  #[msg("Escrow layout version is not supported")]
+  UnsupportedEscrowVersion,
+  #[msg("Escrow already uses the current layout")]
+  EscrowAlreadyMigrated,
+  #[msg("Only a wrapped SOL taker account can be unwrapped")]
+  NotWrappedSol,
+  #[msg("Escrow mints must differ")]
+  SameMint,
+  #[msg("Mint is not owned by the given token program")]
+  MintTokenProgramMismatch,
+  #[msg("Maker token account is frozen")]
+  MakerAccountFrozen,
+  #[msg("Maker token account holds less than the escrow amount")]
+  InsufficientMakerBalance,
+  #[msg("Program is paused")]
+  ProgramPaused,
+  #[msg("Signer is not the config admin")]
+  NotAdmin,
+  #[msg("Signer is not the pending config admin")]
+  NotPendingAdmin,
+  #[msg("Mint is on the deny list")]
+  MintDenied,
+  #[msg("Mint is not on the allow list")]
+  MintNotAllowed,
+  #[msg("Referral share is above 1000 basis points")]
+  InvalidReferralBps,
+  #[msg("Referrer cannot be the maker")]
+  ReferrerIsMaker,
+  #[msg("Referrer and referrer token account must be passed together")]
+  ReferralAccountMismatch,
+  #[msg("Vault is empty, so the new receive must be given")]
+  ReceiveRequired,
+  #[msg("Escrow vault is empty")]
+  EmptyVault,
+  #[msg("Escrow cannot be taken before its start time")]
+  NotStarted,
+  #[msg("Escrow must be passed while it is still open")]
+  EscrowAccountMissing,
+  #[msg("Signer is not the program's upgrade authority")]
+  NotUpgradeAuthority,
+  #[msg("Referrer cannot be the taker")]
+  ReferrerIsTaker,
+  #[msg("Price feed is not owned by the configured oracle program")]
+  UntrustedPriceFeed,
+  #[msg("Escrow asks for more than the taker's max_receive")]
+  ReceiveAboveLimit,
 }
\ No newline at end of file
@@ -0,0 +1,31 @@
+use crate::{errors::EscrowError, state::Config};
+use anchor_lang::prelude::*;
+
+// Second step of the handover, signed by the proposed admin so the role can't
+// be handed to a key nobody controls
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(pending_admin.key()) @ EscrowError::NotPendingAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    fn accept_admin(&mut self) -> Result<()> {
+        self.config.admin = self.pending_admin.key();
+        self.config.pending_admin = None;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    ctx.accounts.accept_admin()?;
+
+    Ok(())
+}
@@ -0,0 +1,293 @@
+use crate::{
+    errors::EscrowError,
+    state::{Config, Escrow, MakerRegistry, MakerStats, PairStats, Quote, Rfq, RfqStatus},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+// Every losing quote is passed as six remaining accounts, in RFQ order:
+// [escrow, vault, maker, maker_ata_a, registry, maker_stats]
+const ACCOUNTS_PER_QUOTE: usize = 6;
+
+// Fills the chosen quote like a take and refunds every other quote to its
+// maker, closing the RFQ in the same transaction.
+#[derive(Accounts)]
+pub struct AcceptQuote<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ EscrowError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        close = taker,
+        seeds = [b"rfq", taker.key().as_ref(), rfq.seed.to_le_bytes().as_ref()],
+        bump = rfq.bump,
+        has_one = taker,
+        has_one = mint_a @ EscrowError::InvalidMintA,
+        has_one = mint_b @ EscrowError::InvalidMintB,
+        constraint = rfq.status == RfqStatus::Open @ EscrowError::RfqNotOpen,
+    )]
+    pub rfq: Box<Account<'info, Rfq>>,
+
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = escrow.load()?.rfq() == Some(rfq.key()) @ EscrowError::QuoteNotFound,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"registry", maker.key().as_ref()],
+        bump = registry.bump,
+        realloc = MakerRegistry::space(registry.escrows.len().saturating_sub(1)),
+        realloc::payer = maker,
+        realloc::zero = false,
+    )]
+    pub registry: Box<Account<'info, MakerRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [b"maker_stats", maker.key().as_ref()],
+        bump = maker_stats.bump,
+    )]
+    pub maker_stats: Box<Account<'info, MakerStats>>,
+
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    // Every quote is in the RFQ's market, so the fill and the losing refunds
+    // share it
+    #[account(
+        mut,
+        seeds = [b"pair_stats", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = pair_stats.bump,
+    )]
+    pub pair_stats: Box<Account<'info, PairStats>>,
+
+    #[account(
+        mut,
+        address = escrow.load()?.vault(&escrow.key(), &token_program.key())?,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AcceptQuote<'info> {
+    fn fill_quote(&mut self) -> Result<()> {
+        let escrow = self.escrow.key();
+        self.registry.untrack(&escrow)?;
+        let receive = self.escrow.load()?.receive;
+
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.taker_ata_b.to_account_info(),
+                    to: self.maker_ata_b.to_account_info(),
+                    mint: self.mint_b.to_account_info(),
+                    authority: self.taker.to_account_info(),
+                },
+            ),
+            receive,
+            self.mint_b.decimals,
+        )?;
+        self.maker_stats.counts.record_filled()?;
+        self.pair_stats.record_fill(self.vault.amount, receive)?;
+
+        self.empty_vault(
+            &self.escrow,
+            &self.vault,
+            self.taker_ata_a.to_account_info(),
+            self.maker.to_account_info(),
+        )
+    }
+
+    fn empty_vault(
+        &self,
+        escrow: &AccountLoader<'info, Escrow>,
+        vault: &InterfaceAccount<'info, TokenAccount>,
+        to: AccountInfo<'info>,
+        maker: AccountInfo<'info>,
+    ) -> Result<()> {
+        let state = *escrow.load()?;
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            state.creator.as_ref(),
+            &state.seed.to_le_bytes()[..],
+            &[state.bump],
+        ]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: vault.to_account_info(),
+                    to,
+                    mint: self.mint_a.to_account_info(),
+                    authority: escrow.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            vault.amount,
+            self.mint_a.decimals,
+        )?;
+
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: vault.to_account_info(),
+                authority: escrow.to_account_info(),
+                destination: maker,
+            },
+            &signer_seeds,
+        ))
+    }
+
+    fn refund_quote(&mut self, quote: &Quote, accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let [escrow_info, vault_info, maker_info, maker_ata_a_info, registry_info, maker_stats_info] =
+            accounts
+        else {
+            return err!(EscrowError::QuoteNotFound);
+        };
+        require_keys_eq!(escrow_info.key(), quote.escrow, EscrowError::QuoteNotFound);
+        require_keys_eq!(maker_info.key(), quote.maker, EscrowError::InvalidMaker);
+
+        let escrow = AccountLoader::<Escrow>::try_from(escrow_info)?;
+        let state = *escrow.load()?;
+        require_keys_eq!(state.maker, quote.maker, EscrowError::InvalidMaker);
+        require_keys_eq!(state.mint_a, self.mint_a.key(), EscrowError::InvalidMintA);
+        require_keys_eq!(
+            vault_info.key(),
+            state.vault(&escrow.key(), &self.token_program.key())?,
+            EscrowError::QuoteNotFound
+        );
+        let vault = InterfaceAccount::<TokenAccount>::try_from(vault_info)?;
+
+        let maker_ata_a = InterfaceAccount::<TokenAccount>::try_from(maker_ata_a_info)?;
+        require_keys_eq!(maker_ata_a.owner, quote.maker, EscrowError::InvalidMaker);
+        require_keys_eq!(
+            maker_ata_a.mint,
+            self.mint_a.key(),
+            EscrowError::InvalidMintA
+        );
+
+        let mut registry = Account::<MakerRegistry>::try_from(registry_info)?;
+        require_keys_eq!(registry.maker, quote.maker, EscrowError::RegistryMismatch);
+
+        // The winner's stats are written back on exit, so a loser sharing its
+        // maker is counted there rather than through a second copy
+        let mut maker_stats = if maker_stats_info.key() == self.maker_stats.key() {
+            None
+        } else {
+            let maker_stats = Account::<MakerStats>::try_from(maker_stats_info)?;
+            require_keys_eq!(maker_stats.maker, quote.maker, EscrowError::InvalidMaker);
+            Some(maker_stats)
+        };
+
+        self.empty_vault(
+            &escrow,
+            &vault,
+            maker_ata_a_info.clone(),
+            maker_info.clone(),
+        )?;
+        escrow.close(maker_info.clone())?;
+
+        registry.untrack(&quote.escrow)?;
+        registry.exit(&crate::ID)?;
+        shrink_registry(registry_info, maker_info)?;
+
+        match &mut maker_stats {
+            Some(maker_stats) => {
+                maker_stats.counts.record_refunded()?;
+                maker_stats.exit(&crate::ID)?;
+            }
+            None => self.maker_stats.counts.record_refunded()?,
+        }
+        self.pair_stats.counts.record_refunded()
+    }
+
+    fn refund_losing_quotes(&mut self, remaining: &'info [AccountInfo<'info>]) -> Result<()> {
+        let winner = self.escrow.key();
+        let quotes = self.rfq.quotes.clone();
+        let losers = quotes.iter().filter(|quote| quote.escrow != winner);
+        let mut chunks = remaining.chunks(ACCOUNTS_PER_QUOTE);
+
+        for quote in losers {
+            let accounts = chunks.next().ok_or(EscrowError::QuoteNotFound)?;
+            self.refund_quote(quote, accounts)?;
+        }
+        require!(chunks.next().is_none(), EscrowError::QuoteNotFound);
+        Ok(())
+    }
+}
+
+// Registries passed as remaining accounts miss out on the realloc constraint,
+// so the freed slot is trimmed and its rent returned to the maker by hand.
+fn shrink_registry<'info>(registry: &AccountInfo<'info>, maker: &AccountInfo<'info>) -> Result<()> {
+    let data_len = registry.data_len() - 32;
+    let excess = registry
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(data_len));
+    registry.resize(data_len)?;
+    registry.sub_lamports(excess)?;
+    maker.add_lamports(excess)?;
+    Ok(())
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptQuote<'info>>) -> Result<()> {
+    ctx.accounts.refund_losing_quotes(ctx.remaining_accounts)?;
+    ctx.accounts.fill_quote()?;
+
+    Ok(())
+}
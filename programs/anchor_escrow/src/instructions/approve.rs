@@ -0,0 +1,37 @@
+use crate::state::{Multisig, Proposal};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    pub approver: Signer<'info>,
+
+    #[account(
+        seeds = [b"multisig", multisig.creator.as_ref(), multisig.seed.to_le_bytes().as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            multisig.key().as_ref(),
+            proposal.index.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        has_one = multisig,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+impl<'info> Approve<'info> {
+    fn approve(&mut self) -> Result<()> {
+        self.proposal.approve(&self.multisig, &self.approver.key())
+    }
+}
+
+pub fn handler(ctx: Context<Approve>) -> Result<()> {
+    ctx.accounts.approve()?;
+
+    Ok(())
+}
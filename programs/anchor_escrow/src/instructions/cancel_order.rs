@@ -0,0 +1,49 @@
+use crate::{
+    errors::EscrowError,
+    state::{ConditionalOrder, Escrow},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"order", order.escrow.as_ref()],
+        bump = order.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = escrow.is_some() || refunded_escrow.is_some() @ EscrowError::EscrowAccountMissing,
+    )]
+    pub order: Account<'info, ConditionalOrder>,
+
+    // Required while the escrow is open, so cancelling can't leave it waiting
+    // on a trigger that will never come
+    #[account(
+        mut,
+        address = order.escrow,
+    )]
+    pub escrow: Option<AccountLoader<'info, Escrow>>,
+
+    // Passed instead of `escrow` once that has been refunded, which hands its
+    // address back to the system program
+    #[account(address = order.escrow)]
+    pub refunded_escrow: Option<SystemAccount<'info>>,
+}
+
+impl<'info> CancelOrder<'info> {
+    fn release_escrow(&mut self) -> Result<()> {
+        if let Some(escrow) = &self.escrow {
+            escrow.load_mut()?.set_trigger_pending(false);
+        }
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CancelOrder>) -> Result<()> {
+    ctx.accounts.release_escrow()?;
+
+    Ok(())
+}
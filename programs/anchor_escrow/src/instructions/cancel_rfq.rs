@@ -0,0 +1,38 @@
+use crate::{
+    errors::EscrowError,
+    state::{Rfq, RfqStatus},
+};
+use anchor_lang::prelude::*;
+
+// Stops an RFQ. Without quotes it closes right away, otherwise it waits for the
+// makers to withdraw theirs.
+#[derive(Accounts)]
+pub struct CancelRfq<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rfq", taker.key().as_ref(), rfq.seed.to_le_bytes().as_ref()],
+        bump = rfq.bump,
+        has_one = taker,
+        constraint = rfq.status == RfqStatus::Open @ EscrowError::RfqNotOpen,
+    )]
+    pub rfq: Account<'info, Rfq>,
+}
+
+impl<'info> CancelRfq<'info> {
+    fn cancel(&mut self) -> Result<()> {
+        if self.rfq.quotes.is_empty() {
+            return self.rfq.close(self.taker.to_account_info());
+        }
+        self.rfq.status = RfqStatus::Cancelled;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CancelRfq>) -> Result<()> {
+    ctx.accounts.cancel()?;
+
+    Ok(())
+}
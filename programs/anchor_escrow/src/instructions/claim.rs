@@ -0,0 +1,126 @@
+use crate::{errors::EscrowError, state::Vesting};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+// Releases whatever part of a vested take has unlocked since the last claim.
+// The final claim closes the vesting vault and account back to the taker.
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", vesting.escrow.as_ref(), beneficiary.key().as_ref()],
+        bump = vesting.bump,
+        has_one = mint @ EscrowError::InvalidMintA,
+    )]
+    pub vesting: Box<Account<'info, Vesting>>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program,
+    )]
+    pub vesting_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program,
+    )]
+    pub beneficiary_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Claim<'info> {
+    fn claimable(&self) -> Result<u64> {
+        let vesting = &self.vesting;
+        let unlocked = vesting.schedule.unlocked(
+            vesting.total,
+            vesting.start,
+            Clock::get()?.unix_timestamp,
+        )?;
+        let claimable = unlocked.saturating_sub(vesting.claimed);
+        require_gt!(claimable, 0, EscrowError::NothingToClaim);
+        Ok(claimable)
+    }
+
+    fn release(&mut self, amount: u64) -> Result<()> {
+        let claimed = self
+            .vesting
+            .claimed
+            .checked_add(amount)
+            .ok_or(EscrowError::MathOverflow)?;
+        let last = claimed == self.vesting.total;
+        // Anyone can send tokens to the vault, so the last claim sweeps all of
+        // it or the vault could never be closed
+        let transfer = if last {
+            self.vesting_vault.amount
+        } else {
+            amount
+        };
+        let escrow = self.vesting.escrow;
+        let beneficiary = self.beneficiary.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"vesting",
+            escrow.as_ref(),
+            beneficiary.as_ref(),
+            &[self.vesting.bump],
+        ]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vesting_vault.to_account_info(),
+                    to: self.beneficiary_ata.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    authority: self.vesting.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            transfer,
+            self.mint.decimals,
+        )?;
+        self.vesting.claimed = claimed;
+
+        if last {
+            close_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: self.vesting_vault.to_account_info(),
+                    authority: self.vesting.to_account_info(),
+                    destination: self.beneficiary.to_account_info(),
+                },
+                &signer_seeds,
+            ))?;
+            self.vesting.close(self.beneficiary.to_account_info())?;
+        }
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<Claim>) -> Result<()> {
+    let amount = ctx.accounts.claimable()?;
+    ctx.accounts.release(amount)?;
+
+    Ok(())
+}
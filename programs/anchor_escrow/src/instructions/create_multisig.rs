@@ -0,0 +1,75 @@
+use crate::{
+    errors::EscrowError,
+    state::{Multisig, MAX_APPROVERS},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = Multisig::INIT_SPACE + Multisig::DISCRIMINATOR.len(),
+        seeds = [b"multisig", creator.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    // Acts as the maker of the multisig's escrows; fund it to pay their rent
+    #[account(
+        seeds = [b"multisig_signer", multisig.key().as_ref()],
+        bump,
+    )]
+    pub signer: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateMultisig<'info> {
+    fn populate_multisig(
+        &mut self,
+        seed: u64,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+        bumps: &CreateMultisigBumps,
+    ) -> Result<()> {
+        let mut distinct = approvers.clone();
+        distinct.sort();
+        distinct.dedup();
+        require!(
+            distinct.len() == approvers.len() && approvers.len() <= MAX_APPROVERS,
+            EscrowError::InvalidApprovers
+        );
+        require!(
+            threshold > 0 && usize::from(threshold) <= approvers.len(),
+            EscrowError::InvalidApprovers
+        );
+
+        self.multisig.set_inner(Multisig {
+            creator: self.creator.key(),
+            seed,
+            approvers,
+            threshold,
+            proposal_count: 0,
+            bump: bumps.multisig,
+            signer_bump: bumps.signer,
+        });
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<CreateMultisig>,
+    seed: u64,
+    approvers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    ctx.accounts
+        .populate_multisig(seed, approvers, threshold, &ctx.bumps)?;
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use crate::{
+    errors::EscrowError,
+    state::{Config, Escrow},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+// Tops up the vault of an open escrow. Tranched, reserved and quoted escrows
+// are sized by terms already promised to a taker, so they are left alone.
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ EscrowError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        has_one = mint_a @ EscrowError::InvalidMintA,
+        constraint = escrow.load()?.tranches().is_none() @ EscrowError::TranchedEscrow,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        address = escrow.load()?.vault(&escrow.key(), &token_program.key())?,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint_a,
+        token::authority = maker,
+        token::token_program = token_program,
+    )]
+    pub maker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Deposit<'info> {
+    fn deposit_tokens(&mut self, amount: u64) -> Result<()> {
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.maker_ata_a.to_account_info(),
+                    mint: self.mint_a.to_account_info(),
+                    to: self.vault.to_account_info(),
+                    authority: self.maker.to_account_info(),
+                },
+            ),
+            amount,
+            self.mint_a.decimals,
+        )
+    }
+
+    fn reprice(&mut self, amount: u64, receive: Option<u64>) -> Result<()> {
+        let before = self.vault.amount;
+        let after = before
+            .checked_add(amount)
+            .ok_or(EscrowError::MathOverflow)?;
+        let mut escrow = self.escrow.load_mut()?;
+        escrow.receive = escrow.rescaled_receive(before, after, receive)?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<Deposit>, amount: u64, receive: Option<u64>) -> Result<()> {
+    require_gt!(amount, 0, EscrowError::InvalidAmount);
+
+    ctx.accounts.reprice(amount, receive)?;
+    ctx.accounts.deposit_tokens(amount)?;
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use crate::{
+    errors::EscrowError,
+    program::AnchorEscrow,
+    state::{Multisig, MultisigAction, Proposal},
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+    InstructionData,
+};
+use anchor_spl::{associated_token::AssociatedToken, token_interface::TokenInterface};
+
+// Runs an approved make proposal by invoking `make` on this program with the
+// multisig's signer PDA as the maker. Anyone can execute once the threshold is
+// reached; the proposal's rent goes back to the proposer.
+#[derive(Accounts)]
+pub struct ExecuteMake<'info> {
+    #[account(
+        seeds = [b"multisig", multisig.creator.as_ref(), multisig.seed.to_le_bytes().as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Box<Account<'info, Multisig>>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [
+            b"proposal",
+            multisig.key().as_ref(),
+            proposal.index.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        has_one = multisig,
+        has_one = proposer,
+        constraint = proposal.is_approved(&multisig) @ EscrowError::ThresholdNotReached,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig_signer", multisig.key().as_ref()],
+        bump = multisig.signer_bump,
+    )]
+    pub maker: SystemAccount<'info>,
+
+    // The accounts below are checked by `make` itself
+    /// CHECK: checked by `make`
+    pub config: UncheckedAccount<'info>,
+    /// CHECK: created by `make`
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+    /// CHECK: checked by `make`
+    #[account(mut)]
+    pub registry: UncheckedAccount<'info>,
+    /// CHECK: checked by `make`
+    #[account(mut)]
+    pub maker_stats: UncheckedAccount<'info>,
+    /// CHECK: checked by `make`
+    #[account(mut)]
+    pub pair_stats: UncheckedAccount<'info>,
+    /// CHECK: checked by `make` and against the proposal
+    pub mint_a: UncheckedAccount<'info>,
+    /// CHECK: checked by `make` and against the proposal
+    pub mint_b: UncheckedAccount<'info>,
+    /// CHECK: checked by `make`
+    pub mint_a_rule: UncheckedAccount<'info>,
+    /// CHECK: checked by `make`
+    pub mint_b_rule: UncheckedAccount<'info>,
+    /// CHECK: checked by `make`
+    #[account(mut)]
+    pub maker_ata_a: UncheckedAccount<'info>,
+    /// CHECK: created by `make`
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    pub escrow_program: Program<'info, AnchorEscrow>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteMake<'info> {
+    fn make(&self) -> Result<()> {
+        let MultisigAction::Make {
+            seed,
+            receive,
+            amount,
+            mint_a,
+            mint_b,
+        } = self.proposal.action
+        else {
+            return err!(EscrowError::ProposalMismatch);
+        };
+        require_keys_eq!(self.mint_a.key(), mint_a, EscrowError::ProposalMismatch);
+        require_keys_eq!(self.mint_b.key(), mint_b, EscrowError::ProposalMismatch);
+
+        let ix = Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::Make {
+                payer: self.maker.key(),
+                maker: self.maker.key(),
+                config: self.config.key(),
+                escrow: self.escrow.key(),
+                registry: self.registry.key(),
+                maker_stats: self.maker_stats.key(),
+                pair_stats: self.pair_stats.key(),
+                mint_a,
+                mint_b,
+                mint_a_rule: self.mint_a_rule.key(),
+                mint_b_rule: self.mint_b_rule.key(),
+                maker_ata_a: self.maker_ata_a.key(),
+                vault: self.vault.key(),
+                associated_token_program: self.associated_token_program.key(),
+                token_program: self.token_program.key(),
+                system_program: self.system_program.key(),
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Make {
+                seed,
+                receive,
+                amount,
+            }
+            .data(),
+        };
+
+        let multisig = self.multisig.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"multisig_signer",
+            multisig.as_ref(),
+            &[self.multisig.signer_bump],
+        ]];
+        invoke_signed(
+            &ix,
+            &[
+                self.maker.to_account_info(),
+                self.config.to_account_info(),
+                self.escrow.to_account_info(),
+                self.registry.to_account_info(),
+                self.maker_stats.to_account_info(),
+                self.pair_stats.to_account_info(),
+                self.mint_a.to_account_info(),
+                self.mint_b.to_account_info(),
+                self.mint_a_rule.to_account_info(),
+                self.mint_b_rule.to_account_info(),
+                self.maker_ata_a.to_account_info(),
+                self.vault.to_account_info(),
+                self.associated_token_program.to_account_info(),
+                self.token_program.to_account_info(),
+                self.system_program.to_account_info(),
+                self.escrow_program.to_account_info(),
+            ],
+            &signer_seeds,
+        )?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ExecuteMake>) -> Result<()> {
+    ctx.accounts.make()?;
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use crate::{
+    errors::EscrowError,
+    program::AnchorEscrow,
+    state::{Multisig, MultisigAction, Proposal},
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+    InstructionData,
+};
+use anchor_spl::{associated_token::AssociatedToken, token_interface::TokenInterface};
+
+// Runs an approved refund proposal by invoking `refund` on this program with the
+// multisig's signer PDA as the maker.
+#[derive(Accounts)]
+pub struct ExecuteRefund<'info> {
+    #[account(
+        seeds = [b"multisig", multisig.creator.as_ref(), multisig.seed.to_le_bytes().as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Box<Account<'info, Multisig>>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [
+            b"proposal",
+            multisig.key().as_ref(),
+            proposal.index.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        has_one = multisig,
+        has_one = proposer,
+        constraint = proposal.is_approved(&multisig) @ EscrowError::ThresholdNotReached,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig_signer", multisig.key().as_ref()],
+        bump = multisig.signer_bump,
+    )]
+    pub maker: SystemAccount<'info>,
+
+    // The accounts below are checked by `refund` itself
+    /// CHECK: checked by `refund` and against the proposal
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+    /// CHECK: checked by `refund`
+    #[account(mut)]
+    pub registry: UncheckedAccount<'info>,
+    /// CHECK: checked by `refund`
+    #[account(mut)]
+    pub maker_stats: UncheckedAccount<'info>,
+    /// CHECK: checked by `refund`
+    #[account(mut)]
+    pub pair_stats: UncheckedAccount<'info>,
+    /// CHECK: checked by `refund`
+    pub mint_a: UncheckedAccount<'info>,
+    /// CHECK: checked by `refund`
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: checked by `refund`
+    #[account(mut)]
+    pub maker_ata_a: UncheckedAccount<'info>,
+
+    pub escrow_program: Program<'info, AnchorEscrow>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteRefund<'info> {
+    fn refund(&self) -> Result<()> {
+        let MultisigAction::Refund { escrow } = self.proposal.action else {
+            return err!(EscrowError::ProposalMismatch);
+        };
+        require_keys_eq!(self.escrow.key(), escrow, EscrowError::ProposalMismatch);
+
+        let ix = Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::Refund {
+                maker: self.maker.key(),
+                escrow,
+                registry: self.registry.key(),
+                maker_stats: self.maker_stats.key(),
+                pair_stats: self.pair_stats.key(),
+                mint_a: self.mint_a.key(),
+                vault: self.vault.key(),
+                maker_ata_a: self.maker_ata_a.key(),
+                associated_token_program: self.associated_token_program.key(),
+                token_program: self.token_program.key(),
+                system_program: self.system_program.key(),
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Refund {}.data(),
+        };
+
+        let multisig = self.multisig.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"multisig_signer",
+            multisig.as_ref(),
+            &[self.multisig.signer_bump],
+        ]];
+        invoke_signed(
+            &ix,
+            &[
+                self.maker.to_account_info(),
+                self.escrow.to_account_info(),
+                self.registry.to_account_info(),
+                self.maker_stats.to_account_info(),
+                self.pair_stats.to_account_info(),
+                self.mint_a.to_account_info(),
+                self.vault.to_account_info(),
+                self.maker_ata_a.to_account_info(),
+                self.associated_token_program.to_account_info(),
+                self.token_program.to_account_info(),
+                self.system_program.to_account_info(),
+                self.escrow_program.to_account_info(),
+            ],
+            &signer_seeds,
+        )?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ExecuteRefund>) -> Result<()> {
+    ctx.accounts.refund()?;
+
+    Ok(())
+}
@@ -0,0 +1,193 @@
+use crate::errors::EscrowError;
+use crate::state::{Config, Escrow, MakerRegistry, MakerStats, PairStats};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+// Hands the vault to the taker up front, then CPIs into a taker-chosen callback
+// that has to get `escrow.receive` of mint_b to the maker before we return.
+#[derive(Accounts)]
+pub struct FlashTake<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump,
+        constraint=!config.paused @ EscrowError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        close=maker,
+        seeds=[b"escrow",escrow.load()?.creator.as_ref(),escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump=escrow.load()?.bump,
+        has_one=maker @ EscrowError::InvalidMaker,
+        has_one=mint_a @ EscrowError::InvalidMintA,
+        has_one=mint_b @ EscrowError::InvalidMintB,
+        constraint=escrow.load()?.is_unrestricted() @ EscrowError::EscrowRestricted,
+        constraint=!escrow.load()?.trigger_pending() @ EscrowError::TriggerPending,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+    #[account(
+        mut,
+        seeds=[b"registry",maker.key().as_ref()],
+        bump=registry.bump,
+        realloc=MakerRegistry::space(registry.escrows.len().saturating_sub(1)),
+        realloc::payer=maker,
+        realloc::zero=false,
+    )]
+    pub registry: Box<Account<'info, MakerRegistry>>,
+    #[account(
+        mut,
+        seeds=[b"maker_stats",maker.key().as_ref()],
+        bump=maker_stats.bump,
+    )]
+    pub maker_stats: Box<Account<'info, MakerStats>>,
+    #[account(
+        mut,
+        seeds=[b"pair_stats",mint_a.key().as_ref(),mint_b.key().as_ref()],
+        bump=pair_stats.bump,
+    )]
+    pub pair_stats: Box<Account<'info, PairStats>>,
+
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        address=escrow.load()?.vault(&escrow.key(), &token_program.key())?,
+        constraint=vault.amount > 0 @ EscrowError::EmptyVault,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer=taker,
+        associated_token::mint=mint_a,
+        associated_token::authority=taker,
+        associated_token::token_program=token_program,
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer=taker,
+        associated_token::mint=mint_b,
+        associated_token::authority=maker,
+        associated_token::token_program=token_program,
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: arbitrary taker program, only ever invoked without escrow signer seeds
+    #[account(
+        executable,
+        constraint=callback_program.key() != crate::ID @ EscrowError::InvalidCallbackProgram,
+    )]
+    pub callback_program: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FlashTake<'info> {
+    fn untrack_escrow(&mut self) -> Result<()> {
+        let escrow = self.escrow.key();
+        self.registry.untrack(&escrow)
+    }
+    // The vault is closed before the callback runs, so a callback that re-enters
+    // take or flash_take on this escrow finds nothing left to pay out
+    fn withdraw_and_close_vault(&mut self) -> Result<()> {
+        let escrow = *self.escrow.load()?;
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            escrow.creator.as_ref(),
+            &escrow.seed.to_le_bytes()[..],
+            &[escrow.bump],
+        ]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vault.to_account_info(),
+                    to: self.taker_ata_a.to_account_info(),
+                    mint: self.mint_a.to_account_info(),
+                    authority: self.escrow.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            self.vault.amount,
+            self.mint_a.decimals,
+        )?;
+
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault.to_account_info(),
+                authority: self.escrow.to_account_info(),
+                destination: self.maker.to_account_info(),
+            },
+            &signer_seeds,
+        ))?;
+        Ok(())
+    }
+    fn invoke_callback(&self, accounts: &[AccountInfo<'info>], data: Vec<u8>) -> Result<()> {
+        let ix = Instruction {
+            program_id: self.callback_program.key(),
+            accounts: accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data,
+        };
+        let mut infos = accounts.to_vec();
+        infos.push(self.callback_program.to_account_info());
+        invoke(&ix, &infos)?;
+        Ok(())
+    }
+    // Returns what the callback paid the maker
+    fn check_repayment(&mut self, balance_before: u64) -> Result<u64> {
+        self.maker_ata_b.reload()?;
+        let repaid = self
+            .maker_ata_b
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(EscrowError::FlashRepaymentMissing)?;
+        require_gte!(
+            repaid,
+            self.escrow.load()?.receive,
+            EscrowError::FlashRepaymentMissing
+        );
+        Ok(repaid)
+    }
+    fn record_fill(&mut self, sold: u64, paid: u64) -> Result<()> {
+        self.maker_stats.counts.record_filled()?;
+        self.pair_stats.record_fill(sold, paid)
+    }
+}
+
+// Remaining accounts are passed straight through to the callback, in order
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FlashTake<'info>>,
+    callback_data: Vec<u8>,
+) -> Result<()> {
+    let balance_before = ctx.accounts.maker_ata_b.amount;
+    let sold = ctx.accounts.vault.amount;
+    ctx.accounts.untrack_escrow()?;
+    ctx.accounts.withdraw_and_close_vault()?;
+    ctx.accounts
+        .invoke_callback(ctx.remaining_accounts, callback_data)?;
+    let paid = ctx.accounts.check_repayment(balance_before)?;
+    ctx.accounts.record_fill(sold, paid)
+}
@@ -0,0 +1,44 @@
+use crate::{
+    errors::EscrowError,
+    state::{Escrow, Reservation},
+};
+use anchor_lang::prelude::*;
+
+// Permissionless once the window has lapsed: the bond and the reservation's
+// rent go to the maker and the escrow is open to everyone again.
+#[derive(Accounts)]
+pub struct ForfeitReservation<'info> {
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"reservation", escrow.key().as_ref()],
+        bump = reservation.bump,
+        has_one = escrow,
+        constraint = !reservation.is_active(Clock::get()?.slot) @ EscrowError::ReservationActive,
+    )]
+    pub reservation: Account<'info, Reservation>,
+}
+
+impl<'info> ForfeitReservation<'info> {
+    fn release_escrow(&mut self) -> Result<()> {
+        self.escrow.load_mut()?.set_reserved(false);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ForfeitReservation>) -> Result<()> {
+    ctx.accounts.release_escrow()?;
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::{errors::EscrowError, program::AnchorEscrow, state::Config};
+use anchor_lang::prelude::*;
+
+// Run once at deployment by the program's upgrade authority, who becomes the
+// config admin and can hand that role on with `propose_admin`.
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ EscrowError::NotUpgradeAuthority,
+    )]
+    pub program: Program<'info, AnchorEscrow>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ EscrowError::NotUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Config::INIT_SPACE + Config::DISCRIMINATOR.len(),
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitConfig<'info> {
+    fn populate_config(&mut self, bump: u8) -> Result<()> {
+        self.config.set_inner(Config {
+            admin: self.admin.key(),
+            pending_admin: None,
+            paused: false,
+            allowlist_only: false,
+            referral_bps: 0,
+            oracle_program: Pubkey::default(),
+            bump,
+        });
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<InitConfig>) -> Result<()> {
+    ctx.accounts.populate_config(ctx.bumps.config)?;
+
+    Ok(())
+}
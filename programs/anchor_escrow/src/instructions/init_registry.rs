@@ -0,0 +1,57 @@
+use crate::state::{EscrowCounts, MakerRegistry, MakerStats};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitRegistry<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub maker: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = MakerRegistry::space(0),
+        seeds = [b"registry", maker.key().as_ref()],
+        bump,
+    )]
+    pub registry: Account<'info, MakerRegistry>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = MakerStats::INIT_SPACE + MakerStats::DISCRIMINATOR.len(),
+        seeds = [b"maker_stats", maker.key().as_ref()],
+        bump,
+    )]
+    pub maker_stats: Account<'info, MakerStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitRegistry<'info> {
+    fn populate_registry(&mut self, bump: u8) -> Result<()> {
+        self.registry.set_inner(MakerRegistry {
+            maker: self.maker.key(),
+            bump,
+            escrows: Vec::new(),
+        });
+        Ok(())
+    }
+
+    fn populate_stats(&mut self, bump: u8) -> Result<()> {
+        self.maker_stats.set_inner(MakerStats {
+            maker: self.maker.key(),
+            counts: EscrowCounts::default(),
+            bump,
+        });
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<InitRegistry>) -> Result<()> {
+    ctx.accounts.populate_registry(ctx.bumps.registry)?;
+    ctx.accounts.populate_stats(ctx.bumps.maker_stats)?;
+
+    Ok(())
+}
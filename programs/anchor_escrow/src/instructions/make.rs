@@ -1,49 +1,95 @@
 use crate::errors::EscrowError;
-use crate::state::Escrow;
+use crate::state::{Config, Escrow, MakerRegistry, MakerStats, MintRule, PairStats, VaultKind};
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, Transfer};
 
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+    associated_token::{self, AssociatedToken, Create},
+    token::TokenAccount as SplTokenAccount,
+    token_interface::{
+        initialize_account3, transfer_checked, InitializeAccount3, Mint, TokenAccount,
+        TokenInterface, TransferChecked,
+    },
 };
 #[derive(Accounts)]
 #[instruction(seed: u64)]
 pub struct Make<'info> {
+    // Pays the escrow, vault and registry rent so that `maker` can be a PDA of
+    // another program that holds no lamports
     #[account(mut)]
+    pub payer: Signer<'info>,
     pub maker: Signer<'info>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump,
+        constraint=!config.paused @ EscrowError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
     #[account(
         init,
-        payer=maker,
-        space=Escrow::INIT_SPACE +Escrow::DISCRIMINATOR.len(),
+        payer=payer,
+        space=Escrow::SPACE,
         seeds=[b"escrow",maker.key().as_ref(),seed.to_le_bytes().as_ref()],
         bump,
     )]
-    pub escrow: Account<'info, Escrow>,
+    pub escrow: AccountLoader<'info, Escrow>,
+    // Created by the maker's first make if `init_registry` was never called
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=MakerRegistry::space(0),
+        seeds=[b"registry",maker.key().as_ref()],
+        bump,
+    )]
+    pub registry: Box<Account<'info, MakerRegistry>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=MakerStats::INIT_SPACE + MakerStats::DISCRIMINATOR.len(),
+        seeds=[b"maker_stats",maker.key().as_ref()],
+        bump,
+    )]
+    pub maker_stats: Box<Account<'info, MakerStats>>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        space=PairStats::INIT_SPACE + PairStats::DISCRIMINATOR.len(),
+        seeds=[b"pair_stats",mint_a.key().as_ref(),mint_b.key().as_ref()],
+        bump,
+    )]
+    pub pair_stats: Box<Account<'info, PairStats>>,
     //Token accounts
+    // Checked by hand rather than with `mint::token_program`, to fail with a
+    // specific error before any token CPI
     #[account(
-      mint::token_program=token_program,
+      constraint=*mint_a.to_account_info().owner == token_program.key() @ EscrowError::MintTokenProgramMismatch,
     )]
     pub mint_a: InterfaceAccount<'info, Mint>,
     #[account(
-        mint::token_program=token_program,
+        constraint=mint_b.key() != mint_a.key() @ EscrowError::SameMint,
+        constraint=*mint_b.to_account_info().owner == token_program.key() @ EscrowError::MintTokenProgramMismatch,
     )]
     pub mint_b: InterfaceAccount<'info, Mint>,
+    // Each mint's allow/deny rule, which may not exist. Required rather than
+    // optional so that leaving out a deny rule can't get a mint past it.
+    /// CHECK: address-checked, parsed by `MintRule::read`
+    #[account(seeds=[b"mint_rule",mint_a.key().as_ref()], bump)]
+    pub mint_a_rule: UncheckedAccount<'info>,
+    /// CHECK: address-checked, parsed by `MintRule::read`
+    #[account(seeds=[b"mint_rule",mint_b.key().as_ref()], bump)]
+    pub mint_b_rule: UncheckedAccount<'info>,
     #[account(
         mut,
         associated_token::mint=mint_a,
         associated_token::authority=maker,
         associated_token::token_program=token_program,
-
+        constraint=!maker_ata_a.is_frozen() @ EscrowError::MakerAccountFrozen,
     )]
     pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
-    #[account(
-        init,
-        payer=maker,
-        associated_token::mint = mint_a,
-        associated_token::authority=escrow,
-        associated_token::token_program=token_program,
-    )]
-    pub vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: created as the escrow's mint_a ATA by `create_vault`, or at
+    /// `[b"vault", escrow]` by `create_pda_vault`
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
@@ -51,17 +97,143 @@ pub struct Make<'info> {
 }
 
 impl<'info> Make<'info> {
-    fn populate_escrow(&mut self, seed: u64, amount: u64, bump: u8) -> Result<()> {
-        self.escrow.set_inner(Escrow {
+    fn check_mint_rules(&self) -> Result<()> {
+        self.config.check_mint(MintRule::read(&self.mint_a_rule)?)?;
+        self.config.check_mint(MintRule::read(&self.mint_b_rule)?)
+    }
+    // Created by hand rather than with an `associated_token` constraint, so that
+    // the bump found along the way can be cached in the escrow
+    fn create_vault(&self) -> Result<u8> {
+        let (vault, vault_bump) = Pubkey::find_program_address(
+            &[
+                self.escrow.key().as_ref(),
+                self.token_program.key().as_ref(),
+                self.mint_a.key().as_ref(),
+            ],
+            &associated_token::ID,
+        );
+        require_keys_eq!(self.vault.key(), vault, ErrorCode::ConstraintAssociated);
+
+        associated_token::create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            Create {
+                payer: self.payer.to_account_info(),
+                associated_token: self.vault.to_account_info(),
+                authority: self.escrow.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))?;
+        Ok(vault_bump)
+    }
+    // Skips the ATA program by creating the account and initializing it through
+    // the token program. Sized for a plain token account, so mints whose
+    // accounts need extensions should keep the ATA vault. Funded, allocated and
+    // assigned in separate steps rather than with `create_account`, which fails
+    // if anyone has already sent lamports to the predictable address.
+    fn create_pda_vault(&self) -> Result<u8> {
+        let escrow = self.escrow.key();
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault", escrow.as_ref()], &crate::ID);
+        require_keys_eq!(self.vault.key(), vault, ErrorCode::ConstraintSeeds);
+
+        let signer_seeds: [&[&[u8]]; 1] = [&[b"vault", escrow.as_ref(), &[vault_bump]]];
+        let shortfall = Rent::get()?
+            .minimum_balance(SplTokenAccount::LEN)
+            .saturating_sub(self.vault.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: self.vault.to_account_info(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Allocate {
+                    account_to_allocate: self.vault.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            SplTokenAccount::LEN as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Assign {
+                    account_to_assign: self.vault.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            &self.token_program.key(),
+        )?;
+        initialize_account3(CpiContext::new(
+            self.token_program.to_account_info(),
+            InitializeAccount3 {
+                account: self.vault.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+        ))?;
+        Ok(vault_bump)
+    }
+    fn populate_escrow(
+        &mut self,
+        seed: u64,
+        amount: u64,
+        bump: u8,
+        vault_kind: VaultKind,
+        vault_bump: u8,
+    ) -> Result<()> {
+        let mut escrow = self.escrow.load_init()?;
+        *escrow = Escrow::new(
             seed,
-            maker: self.maker.key(),
-            mint_a: self.mint_a.key(),
-            mint_b: self.mint_b.key(),
-            receive: amount,
+            self.maker.key(),
+            self.mint_a.key(),
+            self.mint_b.key(),
+            amount,
             bump,
-        });
+            vault_bump,
+        );
+        escrow.set_vault_kind(vault_kind);
+        Ok(())
+    }
+    fn track_escrow(&mut self, registry_bump: u8) -> Result<()> {
+        if self.registry.maker == Pubkey::default() {
+            self.registry.maker = self.maker.key();
+            self.registry.bump = registry_bump;
+        }
+        MakerRegistry::grow(
+            &self.registry,
+            self.payer.to_account_info(),
+            self.system_program.to_account_info(),
+        )?;
+        self.registry.escrows.push(self.escrow.key());
         Ok(())
     }
+    // The market's stats are created by its first make, and so are the
+    // maker's if they have none yet
+    fn record_created(&mut self, maker_stats_bump: u8, pair_stats_bump: u8) -> Result<()> {
+        if self.maker_stats.maker == Pubkey::default() {
+            self.maker_stats.maker = self.maker.key();
+            self.maker_stats.bump = maker_stats_bump;
+        }
+        self.maker_stats.counts.record_created()?;
+
+        if self.pair_stats.mint_a == Pubkey::default() {
+            self.pair_stats.mint_a = self.mint_a.key();
+            self.pair_stats.mint_b = self.mint_b.key();
+            self.pair_stats.bump = pair_stats_bump;
+        }
+        self.pair_stats.counts.record_created()
+    }
     fn deposit_tokens(&mut self, amount: u64) -> Result<()> {
         transfer_checked(
             CpiContext::new(
@@ -80,12 +252,31 @@ impl<'info> Make<'info> {
     }
 }
 
-pub fn handler(ctx: Context<Make>, seed: u64, receive: u64, amount: u64) -> Result<()> {
+pub fn handler(
+    ctx: Context<Make>,
+    seed: u64,
+    receive: u64,
+    amount: u64,
+    vault_kind: VaultKind,
+) -> Result<()> {
     require_gt!(receive, 0, EscrowError::InvalidAmount);
     require_gt!(amount, 0, EscrowError::InvalidAmount);
+    ctx.accounts.check_mint_rules()?;
+    require_gte!(
+        ctx.accounts.maker_ata_a.amount,
+        amount,
+        EscrowError::InsufficientMakerBalance
+    );
 
+    let vault_bump = match vault_kind {
+        VaultKind::Ata => ctx.accounts.create_vault()?,
+        VaultKind::Pda => ctx.accounts.create_pda_vault()?,
+    };
+    ctx.accounts
+        .populate_escrow(seed, receive, ctx.bumps.escrow, vault_kind, vault_bump)?;
+    ctx.accounts.track_escrow(ctx.bumps.registry)?;
     ctx.accounts
-        .populate_escrow(seed, receive, ctx.bumps.escrow)?;
+        .record_created(ctx.bumps.maker_stats, ctx.bumps.pair_stats)?;
     ctx.accounts.deposit_tokens(amount)?;
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,254 @@
+use crate::{
+    errors::EscrowError,
+    state::{Config, Escrow, MakerRegistry, MakerStats, PairStats},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+// Fills an escrow against a standing counter-escrow that sells mint_b for
+// mint_a. Both vaults swap in full, so each side gets at least its `receive`.
+#[derive(Accounts)]
+pub struct MatchOrders<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ EscrowError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        has_one = mint_a @ EscrowError::InvalidMintA,
+        has_one = mint_b @ EscrowError::InvalidMintB,
+        constraint = escrow.load()?.is_unrestricted() @ EscrowError::EscrowRestricted,
+        constraint = !escrow.load()?.trigger_pending() @ EscrowError::TriggerPending,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"registry", maker.key().as_ref()],
+        bump = registry.bump,
+        realloc = MakerRegistry::space(registry.escrows.len().saturating_sub(1)),
+        realloc::payer = maker,
+        realloc::zero = false,
+    )]
+    pub registry: Box<Account<'info, MakerRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [b"maker_stats", maker.key().as_ref()],
+        bump = maker_stats.bump,
+    )]
+    pub maker_stats: Box<Account<'info, MakerStats>>,
+
+    #[account(
+        mut,
+        seeds = [b"pair_stats", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = pair_stats.bump,
+    )]
+    pub pair_stats: Box<Account<'info, PairStats>>,
+
+    // Each side's registry and stats are written back separately, so one maker
+    // can't be on both sides without one copy overwriting the other
+    #[account(
+        mut,
+        constraint = counter_maker.key() != maker.key() @ EscrowError::InvalidMaker,
+    )]
+    pub counter_maker: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = counter_maker,
+        seeds = [
+            b"escrow",
+            counter_escrow.load()?.creator.as_ref(),
+            counter_escrow.load()?.seed.to_le_bytes().as_ref(),
+        ],
+        bump = counter_escrow.load()?.bump,
+        constraint = counter_escrow.load()?.maker == counter_maker.key() @ EscrowError::InvalidMaker,
+        constraint = counter_escrow.load()?.mint_a == mint_b.key() @ EscrowError::InvalidMintB,
+        constraint = counter_escrow.load()?.mint_b == mint_a.key() @ EscrowError::InvalidMintA,
+        constraint = counter_escrow.load()?.is_unrestricted() @ EscrowError::EscrowRestricted,
+        constraint = !counter_escrow.load()?.trigger_pending() @ EscrowError::TriggerPending,
+    )]
+    pub counter_escrow: AccountLoader<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"registry", counter_maker.key().as_ref()],
+        bump = counter_registry.bump,
+        realloc = MakerRegistry::space(counter_registry.escrows.len().saturating_sub(1)),
+        realloc::payer = counter_maker,
+        realloc::zero = false,
+    )]
+    pub counter_registry: Box<Account<'info, MakerRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [b"maker_stats", counter_maker.key().as_ref()],
+        bump = counter_maker_stats.bump,
+    )]
+    pub counter_maker_stats: Box<Account<'info, MakerStats>>,
+
+    // The counter escrow sells mint_b, so its market is the reverse pair
+    #[account(
+        mut,
+        seeds = [b"pair_stats", mint_b.key().as_ref(), mint_a.key().as_ref()],
+        bump = counter_pair_stats.bump,
+    )]
+    pub counter_pair_stats: Box<Account<'info, PairStats>>,
+
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        address = escrow.load()?.vault(&escrow.key(), &token_program.key())?,
+        constraint = vault.amount > 0 @ EscrowError::EmptyVault,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = counter_escrow.load()?.vault(&counter_escrow.key(), &token_program.key())?,
+        constraint = counter_vault.amount > 0 @ EscrowError::EmptyVault,
+    )]
+    pub counter_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        associated_token::mint = mint_a,
+        associated_token::authority = counter_maker,
+        associated_token::token_program = token_program,
+    )]
+    pub counter_maker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MatchOrders<'info> {
+    fn check_cross(&self) -> Result<()> {
+        require_gte!(
+            self.vault.amount,
+            self.counter_escrow.load()?.receive,
+            EscrowError::OrdersDoNotCross
+        );
+        require_gte!(
+            self.counter_vault.amount,
+            self.escrow.load()?.receive,
+            EscrowError::OrdersDoNotCross
+        );
+        Ok(())
+    }
+    fn untrack_escrows(&mut self) -> Result<()> {
+        let escrow = self.escrow.key();
+        self.registry.untrack(&escrow)?;
+        let counter_escrow = self.counter_escrow.key();
+        self.counter_registry.untrack(&counter_escrow)
+    }
+    // Each escrow sells its whole vault for the other's
+    fn record_fills(&mut self) -> Result<()> {
+        let (sold, bought) = (self.vault.amount, self.counter_vault.amount);
+        self.maker_stats.counts.record_filled()?;
+        self.pair_stats.record_fill(sold, bought)?;
+        self.counter_maker_stats.counts.record_filled()?;
+        self.counter_pair_stats.record_fill(bought, sold)
+    }
+    fn settle(
+        &self,
+        escrow: &AccountLoader<'info, Escrow>,
+        vault: &InterfaceAccount<'info, TokenAccount>,
+        mint: &InterfaceAccount<'info, Mint>,
+        to: &InterfaceAccount<'info, TokenAccount>,
+        rent_destination: AccountInfo<'info>,
+    ) -> Result<()> {
+        let state = *escrow.load()?;
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            state.creator.as_ref(),
+            &state.seed.to_le_bytes()[..],
+            &[state.bump],
+        ]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: vault.to_account_info(),
+                    to: to.to_account_info(),
+                    mint: mint.to_account_info(),
+                    authority: escrow.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            vault.amount,
+            mint.decimals,
+        )?;
+
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: vault.to_account_info(),
+                authority: escrow.to_account_info(),
+                destination: rent_destination,
+            },
+            &signer_seeds,
+        ))
+    }
+}
+
+pub fn handler(ctx: Context<MatchOrders>) -> Result<()> {
+    ctx.accounts.check_cross()?;
+    ctx.accounts.untrack_escrows()?;
+    ctx.accounts.record_fills()?;
+
+    let accounts = &ctx.accounts;
+    accounts.settle(
+        &accounts.escrow,
+        &accounts.vault,
+        &accounts.mint_a,
+        &accounts.counter_maker_ata_a,
+        accounts.maker.to_account_info(),
+    )?;
+    accounts.settle(
+        &accounts.counter_escrow,
+        &accounts.counter_vault,
+        &accounts.mint_b,
+        &accounts.maker_ata_b,
+        accounts.counter_maker.to_account_info(),
+    )?;
+
+    Ok(())
+}
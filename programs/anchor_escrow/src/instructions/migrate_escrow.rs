@@ -0,0 +1,145 @@
+use crate::{
+    errors::EscrowError,
+    migration::{self, ESCROW_VERSION},
+    state::{Escrow, MakerRegistry, MakerStats, PairStats},
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token,
+    token_interface::{TokenAccount, TokenInterface},
+};
+
+// Rewrites a legacy escrow in the current layout, resizing the account in
+// place. v1 escrows predate the registry and stats, so an escrow its maker's
+// registry doesn't list yet is also tracked there and counted as created.
+// Anyone may migrate an escrow; the payer covers any extra rent.
+#[derive(Accounts)]
+pub struct MigrateEscrow<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub maker: SystemAccount<'info>,
+
+    /// CHECK: decoded by `migration::decode_escrow` and checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub escrow: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"registry", maker.key().as_ref()],
+        bump = registry.bump,
+        realloc = MakerRegistry::space(registry.escrows.len() + usize::from(!registry.tracks(&escrow.key()))),
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub registry: Box<Account<'info, MakerRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [b"maker_stats", maker.key().as_ref()],
+        bump = maker_stats.bump,
+    )]
+    pub maker_stats: Box<Account<'info, MakerStats>>,
+
+    /// CHECK: checked against the escrow
+    pub mint_a: UncheckedAccount<'info>,
+
+    /// CHECK: checked against the escrow
+    pub mint_b: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PairStats::INIT_SPACE + PairStats::DISCRIMINATOR.len(),
+        seeds = [b"pair_stats", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump,
+    )]
+    pub pair_stats: Box<Account<'info, PairStats>>,
+
+    #[account(
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateEscrow<'info> {
+    fn upgraded_escrow(&self) -> Result<Escrow> {
+        let data = self.escrow.try_borrow_data()?;
+        let version = migration::escrow_version(&data)?;
+        require_neq!(version, ESCROW_VERSION, EscrowError::EscrowAlreadyMigrated);
+        let mut escrow = migration::decode_escrow(&data)?;
+
+        require_keys_eq!(escrow.maker, self.maker.key(), EscrowError::InvalidMaker);
+        require_keys_eq!(escrow.mint_a, self.mint_a.key(), EscrowError::InvalidMintA);
+        require_keys_eq!(escrow.mint_b, self.mint_b.key(), EscrowError::InvalidMintB);
+
+        // `vault` is already checked to be this ATA, so its bump can be cached
+        let (_, vault_bump) = Pubkey::find_program_address(
+            &[
+                self.escrow.key().as_ref(),
+                self.token_program.key().as_ref(),
+                self.mint_a.key().as_ref(),
+            ],
+            &associated_token::ID,
+        );
+        escrow.vault_bump = vault_bump;
+        Ok(escrow)
+    }
+
+    fn write_escrow(&self, escrow: &Escrow) -> Result<()> {
+        let shortfall = Rent::get()?
+            .minimum_balance(Escrow::SPACE)
+            .saturating_sub(self.escrow.lamports());
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: self.escrow.to_account_info(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        self.escrow.resize(Escrow::SPACE)?;
+
+        let mut data = self.escrow.try_borrow_mut_data()?;
+        let (discriminator, body) = data.split_at_mut(Escrow::DISCRIMINATOR.len());
+        discriminator.copy_from_slice(Escrow::DISCRIMINATOR);
+        body.copy_from_slice(bytemuck::bytes_of(escrow));
+        Ok(())
+    }
+
+    fn track_escrow(&mut self, pair_stats_bump: u8) -> Result<()> {
+        if self.registry.tracks(&self.escrow.key()) {
+            return Ok(());
+        }
+        self.registry.escrows.push(self.escrow.key());
+        self.maker_stats.counts.record_created()?;
+
+        if self.pair_stats.mint_a == Pubkey::default() {
+            self.pair_stats.mint_a = self.mint_a.key();
+            self.pair_stats.mint_b = self.mint_b.key();
+            self.pair_stats.bump = pair_stats_bump;
+        }
+        self.pair_stats.counts.record_created()
+    }
+}
+
+pub fn handler(ctx: Context<MigrateEscrow>) -> Result<()> {
+    let escrow = ctx.accounts.upgraded_escrow()?;
+    ctx.accounts.write_escrow(&escrow)?;
+    ctx.accounts.track_escrow(ctx.bumps.pair_stats)?;
+
+    Ok(())
+}
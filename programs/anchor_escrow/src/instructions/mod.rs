@@ -5,4 +5,133 @@ pub mod refund;
 pub use refund::*;
 
 pub mod take;
-pub use take::*;
\ No newline at end of file
+pub use take::*;
+
+pub mod transfer_escrow;
+pub use transfer_escrow::*;
+
+pub mod init_registry;
+pub use init_registry::*;
+
+pub mod refund_all;
+pub use refund_all::*;
+
+pub mod set_allowlist;
+pub use set_allowlist::*;
+
+pub mod set_gate;
+pub use set_gate::*;
+
+pub mod set_oracle_pricing;
+pub use set_oracle_pricing::*;
+
+pub mod set_vesting;
+pub use set_vesting::*;
+
+pub mod set_tranches;
+pub use set_tranches::*;
+
+pub mod place_order;
+pub use place_order::*;
+
+pub mod trigger_order;
+pub use trigger_order::*;
+
+pub mod cancel_order;
+pub use cancel_order::*;
+
+pub mod match_orders;
+pub use match_orders::*;
+
+pub mod flash_take;
+pub use flash_take::*;
+
+pub mod claim;
+pub use claim::*;
+
+pub mod take_tranche;
+pub use take_tranche::*;
+
+pub mod set_reservation_terms;
+pub use set_reservation_terms::*;
+
+pub mod reserve;
+pub use reserve::*;
+
+pub mod forfeit_reservation;
+pub use forfeit_reservation::*;
+
+pub mod open_rfq;
+pub use open_rfq::*;
+
+pub mod submit_quote;
+pub use submit_quote::*;
+
+pub mod withdraw_quote;
+pub use withdraw_quote::*;
+
+pub mod cancel_rfq;
+pub use cancel_rfq::*;
+
+pub mod accept_quote;
+pub use accept_quote::*;
+
+pub mod create_multisig;
+pub use create_multisig::*;
+
+pub mod propose_make;
+pub use propose_make::*;
+
+pub mod propose_refund;
+pub use propose_refund::*;
+
+pub mod approve;
+pub use approve::*;
+
+pub mod execute_make;
+pub use execute_make::*;
+
+pub mod execute_refund;
+pub use execute_refund::*;
+
+pub mod set_solo_take;
+pub use set_solo_take::*;
+
+pub mod migrate_escrow;
+pub use migrate_escrow::*;
+
+pub mod init_config;
+pub use init_config::*;
+
+pub mod set_paused;
+pub use set_paused::*;
+
+pub mod propose_admin;
+pub use propose_admin::*;
+
+pub mod accept_admin;
+pub use accept_admin::*;
+
+pub mod set_mint_rule;
+pub use set_mint_rule::*;
+
+pub mod remove_mint_rule;
+pub use remove_mint_rule::*;
+
+pub mod set_allowlist_only;
+pub use set_allowlist_only::*;
+
+pub mod set_referral_bps;
+pub use set_referral_bps::*;
+
+pub mod deposit;
+pub use deposit::*;
+
+pub mod withdraw;
+pub use withdraw::*;
+
+pub mod set_start_time;
+pub use set_start_time::*;
+
+pub mod set_oracle_program;
+pub use set_oracle_program::*;
@@ -0,0 +1,58 @@
+use crate::{
+    errors::EscrowError,
+    state::{Rfq, RfqStatus},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+// Taker side of the RFQ flow: asks makers to quote `amount` of mint_a for
+// mint_b. Makers answer with escrows through submit_quote.
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct OpenRfq<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    #[account(
+        init,
+        payer = taker,
+        space = Rfq::INIT_SPACE + Rfq::DISCRIMINATOR.len(),
+        seeds = [b"rfq", taker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub rfq: Account<'info, Rfq>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenRfq<'info> {
+    fn populate_rfq(&mut self, seed: u64, amount: u64, bump: u8) -> Result<()> {
+        self.rfq.set_inner(Rfq {
+            taker: self.taker.key(),
+            seed,
+            mint_a: self.mint_a.key(),
+            amount,
+            mint_b: self.mint_b.key(),
+            status: RfqStatus::Open,
+            quotes: Vec::new(),
+            bump,
+        });
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<OpenRfq>, seed: u64, amount: u64) -> Result<()> {
+    require_gt!(amount, 0, EscrowError::InvalidAmount);
+
+    ctx.accounts.populate_rfq(seed, amount, ctx.bumps.rfq)?;
+
+    Ok(())
+}
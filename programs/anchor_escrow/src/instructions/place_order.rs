@@ -0,0 +1,102 @@
+use crate::{
+    errors::EscrowError,
+    state::{ConditionalOrder, Escrow},
+    trigger::OrderKind,
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+#[derive(Accounts)]
+pub struct PlaceOrder<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = maker,
+        space = ConditionalOrder::INIT_SPACE + ConditionalOrder::DISCRIMINATOR.len(),
+        seeds = [b"order", escrow.key().as_ref()],
+        bump,
+    )]
+    pub order: Account<'info, ConditionalOrder>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlaceOrder<'info> {
+    #[allow(clippy::too_many_arguments)]
+    fn populate_order(
+        &mut self,
+        feed: Pubkey,
+        kind: OrderKind,
+        trigger_price: i64,
+        trigger_expo: i32,
+        max_staleness: u64,
+        bounty: u64,
+        bump: u8,
+    ) -> Result<()> {
+        self.order.set_inner(ConditionalOrder {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            feed,
+            kind,
+            trigger_price,
+            trigger_expo,
+            max_staleness,
+            bounty,
+            bump,
+        });
+        self.escrow.load_mut()?.set_trigger_pending(true);
+        Ok(())
+    }
+    fn fund_bounty(&mut self, bounty: u64) -> Result<()> {
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.maker.to_account_info(),
+                    to: self.order.to_account_info(),
+                },
+            ),
+            bounty,
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<PlaceOrder>,
+    feed: Pubkey,
+    kind: OrderKind,
+    trigger_price: i64,
+    trigger_expo: i32,
+    max_staleness: u64,
+    bounty: u64,
+) -> Result<()> {
+    require_gt!(trigger_price, 0, EscrowError::InvalidAmount);
+
+    ctx.accounts.populate_order(
+        feed,
+        kind,
+        trigger_price,
+        trigger_expo,
+        max_staleness,
+        bounty,
+        ctx.bumps.order,
+    )?;
+    ctx.accounts.fund_bounty(bounty)?;
+
+    Ok(())
+}
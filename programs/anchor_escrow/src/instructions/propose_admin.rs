@@ -0,0 +1,30 @@
+use crate::{errors::EscrowError, state::Config};
+use anchor_lang::prelude::*;
+
+// First step of the handover. Proposing again replaces the pending admin, and
+// proposing None withdraws the offer.
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ EscrowError::NotAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> ProposeAdmin<'info> {
+    fn propose_admin(&mut self, new_admin: Option<Pubkey>) -> Result<()> {
+        self.config.pending_admin = new_admin;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ProposeAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.propose_admin(new_admin)?;
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use crate::state::{Multisig, MultisigAction, Proposal};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+// Proposes an escrow made by the multisig's signer PDA. The proposer's
+// approval counts towards the threshold.
+#[derive(Accounts)]
+pub struct ProposeMake<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig", multisig.creator.as_ref(), multisig.seed.to_le_bytes().as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::INIT_SPACE + Proposal::DISCRIMINATOR.len(),
+        seeds = [
+            b"proposal",
+            multisig.key().as_ref(),
+            multisig.proposal_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ProposeMake<'info> {
+    fn populate_proposal(&mut self, action: MultisigAction, bump: u8) -> Result<()> {
+        self.proposal.set_inner(Proposal {
+            multisig: self.multisig.key(),
+            index: self.multisig.proposal_count,
+            proposer: self.proposer.key(),
+            action,
+            approvals: Vec::new(),
+            bump,
+        });
+        self.proposal
+            .approve(&self.multisig, &self.proposer.key())?;
+        self.multisig.proposal_count += 1;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ProposeMake>, seed: u64, receive: u64, amount: u64) -> Result<()> {
+    let action = MultisigAction::Make {
+        seed,
+        receive,
+        amount,
+        mint_a: ctx.accounts.mint_a.key(),
+        mint_b: ctx.accounts.mint_b.key(),
+    };
+    ctx.accounts.populate_proposal(action, ctx.bumps.proposal)?;
+
+    Ok(())
+}
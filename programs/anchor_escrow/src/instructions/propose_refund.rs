@@ -0,0 +1,70 @@
+use crate::{
+    errors::EscrowError,
+    state::{Escrow, Multisig, MultisigAction, Proposal},
+};
+use anchor_lang::prelude::*;
+
+// Proposes refunding one of the multisig's escrows. The proposer's approval
+// counts towards the threshold.
+#[derive(Accounts)]
+pub struct ProposeRefund<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig", multisig.creator.as_ref(), multisig.seed.to_le_bytes().as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::INIT_SPACE + Proposal::DISCRIMINATOR.len(),
+        seeds = [
+            b"proposal",
+            multisig.key().as_ref(),
+            multisig.proposal_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [b"multisig_signer", multisig.key().as_ref()],
+        bump = multisig.signer_bump,
+    )]
+    pub maker: SystemAccount<'info>,
+
+    #[account(has_one = maker @ EscrowError::InvalidMaker)]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ProposeRefund<'info> {
+    fn populate_proposal(&mut self, action: MultisigAction, bump: u8) -> Result<()> {
+        self.proposal.set_inner(Proposal {
+            multisig: self.multisig.key(),
+            index: self.multisig.proposal_count,
+            proposer: self.proposer.key(),
+            action,
+            approvals: Vec::new(),
+            bump,
+        });
+        self.proposal
+            .approve(&self.multisig, &self.proposer.key())?;
+        self.multisig.proposal_count += 1;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ProposeRefund>) -> Result<()> {
+    let action = MultisigAction::Refund {
+        escrow: ctx.accounts.escrow.key(),
+    };
+    ctx.accounts.populate_proposal(action, ctx.bumps.proposal)?;
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use crate::{errors::EscrowError, state::Escrow};
+use crate::{
+    errors::EscrowError,
+    state::{Escrow, MakerRegistry, MakerStats, PairStats},
+};
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
@@ -13,24 +16,47 @@ pub struct Refund<'info> {
     #[account(mut)]
     pub maker: Signer<'info>,
 
+    // Trusted on its owner and discriminator alone, as in `Take`; the registry
+    // and stats are likewise matched on their fields
     #[account(
         mut,
         close = maker,
-        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
-        bump = escrow.bump,
         has_one = maker @ EscrowError::InvalidMaker,
         has_one = mint_a @ EscrowError::InvalidMintA,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(
+        mut,
+        has_one = maker @ EscrowError::InvalidMaker,
+        realloc = MakerRegistry::space(registry.escrows.len().saturating_sub(1)),
+        realloc::payer = maker,
+        realloc::zero = false,
+    )]
+    pub registry: Account<'info, MakerRegistry>,
+
+    #[account(
+        mut,
+        has_one = maker @ EscrowError::InvalidMaker,
+    )]
+    pub maker_stats: Account<'info, MakerStats>,
+
+    #[account(
+        mut,
+        has_one = mint_a @ EscrowError::InvalidMintA,
+        constraint = pair_stats.mint_b == escrow.load()?.mint_b @ EscrowError::InvalidMintB,
     )]
-    pub escrow: Box<Account<'info, Escrow>>,
+    pub pair_stats: Account<'info, PairStats>,
 
     #[account(mint::token_program = token_program)]
     pub mint_a: Box<InterfaceAccount<'info, Mint>>,
 
+    // Token accounts stay boxed to keep try_accounts within the stack limit
     #[account(
         mut,
-        associated_token::mint = mint_a,
-        associated_token::authority = escrow,
-        associated_token::token_program = token_program
+        address = escrow.load()?.vault(&escrow.key(), &token_program.key())?,
     )]
     pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
@@ -51,12 +77,23 @@ pub struct Refund<'info> {
 }
 
 impl<'info> Refund<'info> {
+    fn untrack_escrow(&mut self) -> Result<()> {
+        let escrow = self.escrow.key();
+        self.registry.untrack(&escrow)
+    }
+
+    fn record_refund(&mut self) -> Result<()> {
+        self.maker_stats.counts.record_refunded()?;
+        self.pair_stats.counts.record_refunded()
+    }
+
     fn withdraw_and_close_vault(&mut self) -> Result<()> {
+        let escrow = *self.escrow.load()?;
         let signer_seeds: [&[&[u8]]; 1] = [&[
             b"escrow",
-            self.maker.to_account_info().key.as_ref(),
-            &self.escrow.seed.to_le_bytes()[..],
-            &[self.escrow.bump],
+            escrow.creator.as_ref(),
+            &escrow.seed.to_le_bytes()[..],
+            &[escrow.bump],
         ]];
 
         transfer_checked(
@@ -89,6 +126,8 @@ impl<'info> Refund<'info> {
 }
 
 pub fn handler(ctx: Context<Refund>) -> Result<()> {
+    ctx.accounts.untrack_escrow()?;
+    ctx.accounts.record_refund()?;
     ctx.accounts.withdraw_and_close_vault()?;
 
     Ok(())
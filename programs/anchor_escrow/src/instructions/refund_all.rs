@@ -0,0 +1,134 @@
+use crate::{
+    errors::EscrowError,
+    state::{Escrow, MakerRegistry, MakerStats, PairStats},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+// Every listed escrow is passed as five remaining accounts, in registry order:
+// [escrow, mint_a, vault, maker_ata_a, pair_stats]
+const ACCOUNTS_PER_ESCROW: usize = 5;
+
+#[derive(Accounts)]
+pub struct RefundAll<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"registry", maker.key().as_ref()],
+        bump = registry.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        realloc = MakerRegistry::space(0),
+        realloc::payer = maker,
+        realloc::zero = false,
+    )]
+    pub registry: Box<Account<'info, MakerRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [b"maker_stats", maker.key().as_ref()],
+        bump = maker_stats.bump,
+    )]
+    pub maker_stats: Box<Account<'info, MakerStats>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefundAll<'info> {
+    fn refund_escrow(&mut self, key: &Pubkey, accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let [escrow_info, mint_a_info, vault_info, maker_ata_a_info, pair_stats_info] = accounts
+        else {
+            return err!(EscrowError::RegistryMismatch);
+        };
+        require_keys_eq!(escrow_info.key(), *key, EscrowError::RegistryMismatch);
+
+        let escrow = AccountLoader::<Escrow>::try_from(escrow_info)?;
+        let state = *escrow.load()?;
+        require_keys_eq!(state.maker, self.maker.key(), EscrowError::InvalidMaker);
+        require_keys_eq!(state.mint_a, mint_a_info.key(), EscrowError::InvalidMintA);
+        require!(!state.reserved(), EscrowError::EscrowReserved);
+        require!(state.rfq().is_none(), EscrowError::EscrowQuoted);
+
+        let mint_a = InterfaceAccount::<Mint>::try_from(mint_a_info)?;
+        require_keys_eq!(
+            vault_info.key(),
+            state.vault(&escrow.key(), &self.token_program.key())?,
+            EscrowError::RegistryMismatch
+        );
+        let vault = InterfaceAccount::<TokenAccount>::try_from(vault_info)?;
+
+        let maker_ata_a = InterfaceAccount::<TokenAccount>::try_from(maker_ata_a_info)?;
+        require_keys_eq!(
+            maker_ata_a.owner,
+            self.maker.key(),
+            EscrowError::InvalidMaker
+        );
+        require_keys_eq!(maker_ata_a.mint, mint_a.key(), EscrowError::InvalidMintA);
+
+        // Escrows of the same market share one, written back before the next loads it
+        let mut pair_stats = Account::<PairStats>::try_from(pair_stats_info)?;
+        require_keys_eq!(pair_stats.mint_a, state.mint_a, EscrowError::InvalidMintA);
+        require_keys_eq!(pair_stats.mint_b, state.mint_b, EscrowError::InvalidMintB);
+        self.maker_stats.counts.record_refunded()?;
+        pair_stats.counts.record_refunded()?;
+        pair_stats.exit(&crate::ID)?;
+
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            state.creator.as_ref(),
+            &state.seed.to_le_bytes()[..],
+            &[state.bump],
+        ]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: vault.to_account_info(),
+                    to: maker_ata_a.to_account_info(),
+                    mint: mint_a.to_account_info(),
+                    authority: escrow.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            vault.amount,
+            mint_a.decimals,
+        )?;
+
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: vault.to_account_info(),
+                authority: escrow.to_account_info(),
+                destination: self.maker.to_account_info(),
+            },
+            &signer_seeds,
+        ))?;
+
+        escrow.close(self.maker.to_account_info())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RefundAll<'info>>) -> Result<()> {
+    let escrows = std::mem::take(&mut ctx.accounts.registry.escrows);
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        escrows.len() * ACCOUNTS_PER_ESCROW,
+        EscrowError::RegistryMismatch
+    );
+
+    for (key, accounts) in escrows
+        .iter()
+        .zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_ESCROW))
+    {
+        ctx.accounts.refund_escrow(key, accounts)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use crate::{
+    errors::EscrowError,
+    state::{Config, MintRule},
+};
+use anchor_lang::prelude::*;
+
+// Takes a mint off whichever list it is on
+#[derive(Accounts)]
+pub struct RemoveMintRule<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ EscrowError::NotAdmin,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"mint_rule", mint_rule.mint.as_ref()],
+        bump = mint_rule.bump,
+    )]
+    pub mint_rule: Account<'info, MintRule>,
+}
+
+pub fn handler(_ctx: Context<RemoveMintRule>) -> Result<()> {
+    Ok(())
+}
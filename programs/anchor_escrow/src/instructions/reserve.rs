@@ -0,0 +1,81 @@
+use crate::{
+    errors::EscrowError,
+    state::{Escrow, Reservation},
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+// Locks the escrow to the taker for the maker's reservation window. The bond
+// comes back with a completed take and goes to the maker if the window lapses.
+#[derive(Accounts)]
+pub struct Reserve<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        constraint = escrow.load()?.reservation_terms().is_some() @ EscrowError::ReservationsDisabled,
+        // accept_quote closes a quoted escrow without settling any reservation
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = taker,
+        space = Reservation::INIT_SPACE + Reservation::DISCRIMINATOR.len(),
+        seeds = [b"reservation", escrow.key().as_ref()],
+        bump,
+    )]
+    pub reservation: Account<'info, Reservation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Reserve<'info> {
+    fn populate_reservation(&mut self, bump: u8) -> Result<()> {
+        let terms = self
+            .escrow
+            .load()?
+            .reservation_terms()
+            .ok_or(EscrowError::ReservationsDisabled)?;
+        let expires_slot = Clock::get()?
+            .slot
+            .checked_add(terms.window)
+            .ok_or(EscrowError::MathOverflow)?;
+
+        self.reservation.set_inner(Reservation {
+            escrow: self.escrow.key(),
+            taker: self.taker.key(),
+            bond: terms.bond,
+            expires_slot,
+            bump,
+        });
+        self.escrow.load_mut()?.set_reserved(true);
+        Ok(())
+    }
+
+    fn post_bond(&self) -> Result<()> {
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.taker.to_account_info(),
+                    to: self.reservation.to_account_info(),
+                },
+            ),
+            self.reservation.bond,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<Reserve>) -> Result<()> {
+    ctx.accounts.populate_reservation(ctx.bumps.reservation)?;
+    ctx.accounts.post_bond()?;
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::{errors::EscrowError, state::Escrow};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAllowlist<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+}
+
+impl<'info> SetAllowlist<'info> {
+    fn set_root(&mut self, root: Option<[u8; 32]>) -> Result<()> {
+        self.escrow.load_mut()?.set_allowlist_root(root);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetAllowlist>, root: Option<[u8; 32]>) -> Result<()> {
+    ctx.accounts.set_root(root)?;
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::{errors::EscrowError, state::Config};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAllowlistOnly<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ EscrowError::NotAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> SetAllowlistOnly<'info> {
+    fn set_allowlist_only(&mut self, enabled: bool) -> Result<()> {
+        self.config.allowlist_only = enabled;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetAllowlistOnly>, enabled: bool) -> Result<()> {
+    ctx.accounts.set_allowlist_only(enabled)?;
+
+    Ok(())
+}
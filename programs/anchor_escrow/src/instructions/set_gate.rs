@@ -0,0 +1,36 @@
+use crate::{errors::EscrowError, state::Escrow};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetGate<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+}
+
+impl<'info> SetGate<'info> {
+    fn set_gate(&mut self, gate_mint: Option<Pubkey>, min_gate_balance: u64) -> Result<()> {
+        let mut escrow = self.escrow.load_mut()?;
+        escrow.set_gate_mint(gate_mint);
+        escrow.min_gate_balance = min_gate_balance;
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<SetGate>,
+    gate_mint: Option<Pubkey>,
+    min_gate_balance: u64,
+) -> Result<()> {
+    ctx.accounts.set_gate(gate_mint, min_gate_balance)?;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::{
+    errors::EscrowError,
+    state::{Config, MintListing, MintRule},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+// Adds a mint to the allow or deny list, moving it across if it is already
+// on the other one
+#[derive(Accounts)]
+pub struct SetMintRule<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ EscrowError::NotAdmin,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = MintRule::INIT_SPACE + MintRule::DISCRIMINATOR.len(),
+        seeds = [b"mint_rule", mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_rule: Account<'info, MintRule>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetMintRule<'info> {
+    fn set_mint_rule(&mut self, listing: MintListing, bump: u8) -> Result<()> {
+        self.mint_rule.set_inner(MintRule {
+            mint: self.mint.key(),
+            listing,
+            bump,
+        });
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetMintRule>, listing: MintListing) -> Result<()> {
+    ctx.accounts.set_mint_rule(listing, ctx.bumps.mint_rule)?;
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::{errors::EscrowError, oracle::OraclePricing, state::Escrow};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetOraclePricing<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+}
+
+impl<'info> SetOraclePricing<'info> {
+    fn set_pricing(&mut self, pricing: Option<OraclePricing>) -> Result<()> {
+        self.escrow.load_mut()?.set_oracle(pricing);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetOraclePricing>, pricing: Option<OraclePricing>) -> Result<()> {
+    ctx.accounts.set_pricing(pricing)?;
+
+    Ok(())
+}
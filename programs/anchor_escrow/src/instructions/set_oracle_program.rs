@@ -0,0 +1,28 @@
+use crate::{errors::EscrowError, state::Config};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetOracleProgram<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ EscrowError::NotAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> SetOracleProgram<'info> {
+    fn set_oracle_program(&mut self, oracle_program: Pubkey) -> Result<()> {
+        self.config.oracle_program = oracle_program;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetOracleProgram>, oracle_program: Pubkey) -> Result<()> {
+    ctx.accounts.set_oracle_program(oracle_program)?;
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::{errors::EscrowError, state::Config};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ EscrowError::NotAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> SetPaused<'info> {
+    fn set_paused(&mut self, paused: bool) -> Result<()> {
+        self.config.paused = paused;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    ctx.accounts.set_paused(paused)?;
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::{errors::EscrowError, state::Config};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetReferralBps<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ EscrowError::NotAdmin,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> SetReferralBps<'info> {
+    fn set_referral_bps(&mut self, referral_bps: u16) -> Result<()> {
+        require_gte!(
+            Config::MAX_REFERRAL_BPS,
+            referral_bps,
+            EscrowError::InvalidReferralBps
+        );
+        self.config.referral_bps = referral_bps;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetReferralBps>, referral_bps: u16) -> Result<()> {
+    ctx.accounts.set_referral_bps(referral_bps)?;
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::{
+    errors::EscrowError,
+    state::{Escrow, ReservationTerms},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetReservationTerms<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+}
+
+impl<'info> SetReservationTerms<'info> {
+    fn set_terms(&mut self, terms: Option<ReservationTerms>) -> Result<()> {
+        if let Some(terms) = &terms {
+            // A free reservation could be renewed back to back to lock the
+            // escrow up indefinitely
+            require_gt!(terms.bond, 0, EscrowError::InvalidAmount);
+            require_gt!(terms.window, 0, EscrowError::InvalidAmount);
+        }
+        self.escrow.load_mut()?.set_reservation_terms(terms);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetReservationTerms>, terms: Option<ReservationTerms>) -> Result<()> {
+    ctx.accounts.set_terms(terms)?;
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::{errors::EscrowError, state::Escrow};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetSoloTake<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+}
+
+impl<'info> SetSoloTake<'info> {
+    fn set_solo_take(&mut self, enabled: bool) -> Result<()> {
+        self.escrow.load_mut()?.set_solo_take(enabled);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetSoloTake>, enabled: bool) -> Result<()> {
+    ctx.accounts.set_solo_take(enabled)?;
+
+    Ok(())
+}
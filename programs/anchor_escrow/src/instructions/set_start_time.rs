@@ -0,0 +1,33 @@
+use crate::{
+    errors::EscrowError,
+    state::{Escrow, StartTime},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetStartTime<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+}
+
+impl<'info> SetStartTime<'info> {
+    fn set_start_time(&mut self, start: Option<StartTime>) -> Result<()> {
+        self.escrow.load_mut()?.set_start_time(start);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetStartTime>, start: Option<StartTime>) -> Result<()> {
+    ctx.accounts.set_start_time(start)?;
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::{
+    errors::EscrowError,
+    state::Escrow,
+    tranche::{TrancheSchedule, Tranches},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetTranches<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+}
+
+impl<'info> SetTranches<'info> {
+    fn set_schedule(&mut self, schedule: Option<TrancheSchedule>) -> Result<()> {
+        let mut escrow = self.escrow.load_mut()?;
+        if let Some(tranches) = escrow.tranches() {
+            require_eq!(tranches.index, 0, EscrowError::TranchesStarted);
+        }
+        if let Some(schedule) = &schedule {
+            schedule.validate()?;
+        }
+        escrow.set_tranches(schedule.map(Tranches::new));
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetTranches>, schedule: Option<TrancheSchedule>) -> Result<()> {
+    ctx.accounts.set_schedule(schedule)?;
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::{errors::EscrowError, state::Escrow, vesting::VestingSchedule};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetVesting<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+}
+
+impl<'info> SetVesting<'info> {
+    fn set_schedule(&mut self, schedule: Option<VestingSchedule>) -> Result<()> {
+        if let Some(schedule) = &schedule {
+            schedule.validate()?;
+        }
+        self.escrow.load_mut()?.set_vesting(schedule);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetVesting>, schedule: Option<VestingSchedule>) -> Result<()> {
+    ctx.accounts.set_schedule(schedule)?;
+
+    Ok(())
+}
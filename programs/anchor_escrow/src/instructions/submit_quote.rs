@@ -0,0 +1,79 @@
+use crate::{
+    errors::EscrowError,
+    state::{Config, Escrow, Quote, Rfq, RfqStatus, MAX_QUOTES},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+// Links one of the maker's escrows to an open RFQ as a quote. Until it is
+// withdrawn the escrow can only be filled through accept_quote.
+#[derive(Accounts)]
+pub struct SubmitQuote<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ EscrowError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = escrow.load()?.mint_a == rfq.mint_a @ EscrowError::QuoteDoesNotMatchRfq,
+        constraint = escrow.load()?.mint_b == rfq.mint_b @ EscrowError::QuoteDoesNotMatchRfq,
+        constraint = escrow.load()?.is_unrestricted() @ EscrowError::EscrowRestricted,
+        constraint = !escrow.load()?.trigger_pending() @ EscrowError::TriggerPending,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(
+        address = escrow.load()?.vault(&escrow.key(), &token_program.key())?,
+        constraint = vault.amount >= rfq.amount @ EscrowError::QuoteDoesNotMatchRfq,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"rfq", rfq.taker.as_ref(), rfq.seed.to_le_bytes().as_ref()],
+        bump = rfq.bump,
+        constraint = rfq.status == RfqStatus::Open @ EscrowError::RfqNotOpen,
+    )]
+    pub rfq: Account<'info, Rfq>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SubmitQuote<'info> {
+    fn link_quote(&mut self) -> Result<()> {
+        require_gt!(
+            MAX_QUOTES,
+            self.rfq.quotes.len(),
+            EscrowError::TooManyQuotes
+        );
+        // One quote per maker keeps every loser's registry distinct in accept_quote
+        require!(
+            self.rfq
+                .quotes
+                .iter()
+                .all(|quote| quote.maker != self.maker.key()),
+            EscrowError::DuplicateQuote
+        );
+
+        self.rfq.quotes.push(Quote {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+        });
+        self.escrow.load_mut()?.set_rfq(Some(self.rfq.key()));
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SubmitQuote>) -> Result<()> {
+    ctx.accounts.link_quote()?;
+
+    Ok(())
+}
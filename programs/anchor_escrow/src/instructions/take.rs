@@ -1,98 +1,326 @@
 use crate::errors::EscrowError;
-use crate::state::Escrow;
+use crate::{introspection, merkle, oracle};
+use crate::state::{Config, Escrow, MakerRegistry, MakerStats, PairStats, Reservation, Vesting};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
 
 use anchor_spl::{
     associated_token::AssociatedToken,
+    token::spl_token,
+    token_2022::spl_token_2022,
     token_interface::{
         close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
         TransferChecked,
     },
 };
+// Closes taker accounts that take leaves behind, refunding their rent to the
+// taker in the same instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TakeCleanup {
+    // Close `taker_ata_b` if paying the maker emptied it
+    pub close_taker_ata_b: bool,
+    // Close `taker_ata_a`, which must hold wrapped SOL, so the proceeds arrive
+    // as lamports
+    pub unwrap_sol: bool,
+}
+
 #[derive(Accounts)]
 pub struct Take<'info> {
+    // Pays for any accounts take creates, so `taker` can be a PDA of another
+    // program that holds no lamports
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // Writable to get a reservation bond back
     #[account(mut)]
     pub taker: Signer<'info>,
     #[account(mut)]
     pub maker: SystemAccount<'info>,
+    // Refund takes no config, so makers can still exit while this is paused
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump,
+        constraint=!config.paused @ EscrowError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    // Only make creates escrows, at their PDA, so the owner and discriminator
+    // checks are enough to trust it without deriving its address again. The
+    // same goes for the registry and stats below, which are matched on their
+    // fields instead of their seeds.
     #[account(
         mut,
         close=maker,
-        seeds=[b"escrow",maker.key().as_ref(),escrow.seed.to_le_bytes().as_ref()],
-        bump=escrow.bump,
         has_one=maker @ EscrowError::InvalidMaker,
         has_one=mint_a @ EscrowError::InvalidMintA,
         has_one=mint_b @ EscrowError::InvalidMintB,
+        constraint=escrow.load()?.gate_mint().is_none() || taker_gate_account.is_some() @ EscrowError::GateAccountMissing,
+        constraint=escrow.load()?.oracle().is_none() || price_feed.is_some() @ EscrowError::PriceFeedMissing,
+        constraint=!escrow.load()?.trigger_pending() @ EscrowError::TriggerPending,
+        constraint=escrow.load()?.tranches().is_none() @ EscrowError::TranchedEscrow,
+        constraint=escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+        constraint=!escrow.load()?.reserved() || reservation.is_some() @ EscrowError::ReservationMissing,
+        constraint=escrow.load()?.vesting().is_some() == vesting.is_some() && vesting.is_some() == vesting_vault.is_some() @ EscrowError::VestingAccountMismatch,
+        constraint=!escrow.load()?.solo_take() || instructions_sysvar.is_some() @ EscrowError::InstructionsSysvarMissing,
+        constraint=referrer.is_some() == referrer_ata_b.is_some() @ EscrowError::ReferralAccountMismatch,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+    #[account(
+        mut,
+        has_one=maker @ EscrowError::InvalidMaker,
+        realloc=MakerRegistry::space(registry.escrows.len().saturating_sub(1)),
+        realloc::payer=maker,
+        realloc::zero=false,
     )]
-    pub escrow: Box<Account<'info, Escrow>>,
+    pub registry: Account<'info, MakerRegistry>,
+    #[account(
+        mut,
+        has_one=maker @ EscrowError::InvalidMaker,
+    )]
+    pub maker_stats: Account<'info, MakerStats>,
+    #[account(
+        mut,
+        has_one=mint_a @ EscrowError::InvalidMintA,
+        has_one=mint_b @ EscrowError::InvalidMintB,
+    )]
+    pub pair_stats: Account<'info, PairStats>,
 
     pub mint_a: Box<InterfaceAccount<'info, Mint>>,
     pub mint_b: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         mut,
-        associated_token::mint=mint_a,
-        associated_token::authority=escrow,
-        associated_token::token_program=token_program,
+        address=escrow.load()?.vault(&escrow.key(), &token_program.key())?,
+        // A maker can withdraw the vault down to nothing, see `Withdraw`
+        constraint=vault.amount > 0 @ EscrowError::EmptyVault,
     )]
-    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
         init_if_needed,
-        payer=taker,
+        payer=payer,
         associated_token::mint=mint_a,
         associated_token::authority=taker,
         associated_token::token_program=token_program,
     )]
-    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub taker_ata_a: InterfaceAccount<'info, TokenAccount>,
     #[account(
        mut,
        associated_token::mint=mint_b,
        associated_token::authority=taker,
        associated_token::token_program=token_program,
     )]
-    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub taker_ata_b: InterfaceAccount<'info, TokenAccount>,
     #[account(
         init_if_needed,
-        payer=taker,
+        payer=payer,
         associated_token::mint=mint_b,
         associated_token::authority=maker,
         associated_token::token_program=token_program,
     )]
-    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub maker_ata_b: InterfaceAccount<'info, TokenAccount>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+    // The optional accounts stay boxed, which costs nothing when they're absent
+    // and keeps try_accounts within the stack limit when they're passed
+    #[account(
+        constraint=escrow.load()?.gate_mint() == Some(taker_gate_account.mint) @ EscrowError::InvalidGateMint,
+        constraint=taker_gate_account.owner == taker.key() @ EscrowError::InvalidGateOwner,
+        constraint=taker_gate_account.amount >= escrow.load()?.min_gate_balance @ EscrowError::InsufficientGateBalance,
+    )]
+    pub taker_gate_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// CHECK: must be the feed pinned in `escrow.oracle()`, parsed by `oracle::parse_price`
+    #[account(
+        constraint=escrow.load()?.oracle().map(|o| o.feed) == Some(price_feed.key()) @ EscrowError::InvalidPriceFeed,
+        constraint=price_feed.owner == &config.oracle_program @ EscrowError::UntrustedPriceFeed,
+    )]
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    #[account(
+        init,
+        payer=payer,
+        space=Vesting::INIT_SPACE + Vesting::DISCRIMINATOR.len(),
+        seeds=[b"vesting",escrow.key().as_ref(),taker.key().as_ref()],
+        bump,
+    )]
+    pub vesting: Option<Box<Account<'info, Vesting>>>,
+    // Anyone can create this ATA ahead of the take, so it may already exist
+    #[account(
+        init_if_needed,
+        payer=payer,
+        associated_token::mint=mint_a,
+        associated_token::authority=vesting,
+        associated_token::token_program=token_program,
+    )]
+    pub vesting_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        seeds=[b"reservation",escrow.key().as_ref()],
+        bump=reservation.bump,
+        has_one=escrow,
+    )]
+    pub reservation: Option<Account<'info, Reservation>>,
+    /// CHECK: address-checked, parsed by `introspection::ensure_alone`
+    #[account(address=instructions_sysvar::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+    /// CHECK: only identifies who owns `referrer_ata_b`
+    #[account(
+        constraint=referrer.key() != maker.key() @ EscrowError::ReferrerIsMaker,
+        constraint=referrer.key() != taker.key() @ EscrowError::ReferrerIsTaker,
+    )]
+    pub referrer: Option<UncheckedAccount<'info>>,
+    // Receives `config.referral_bps` of the payment, which the taker pays on top
+    #[account(
+        mut,
+        associated_token::mint=mint_b,
+        associated_token::authority=referrer,
+        associated_token::token_program=token_program,
+    )]
+    pub referrer_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 }
 
 impl<'info> Take<'info> {
-    fn transfer_to_maker(&mut self) -> Result<()> {
+    // Refund has no such check, so a maker can pull an escrow before it opens
+    fn check_start_time(&self, escrow: &Escrow) -> Result<()> {
+        if let Some(start) = escrow.start_time() {
+            require!(start.has_passed(&Clock::get()?), EscrowError::NotStarted);
+        }
+        Ok(())
+    }
+    fn check_solo_take(&self, escrow: &Escrow) -> Result<()> {
+        match &self.instructions_sysvar {
+            Some(instructions_sysvar) if escrow.solo_take() => {
+                introspection::ensure_alone(instructions_sysvar)
+            }
+            _ => Ok(()),
+        }
+    }
+    fn check_allowlist(&self, escrow: &Escrow, proof: &[[u8; 32]]) -> Result<()> {
+        if let Some(root) = escrow.allowlist_root() {
+            require_gte!(merkle::MAX_PROOF_LEN, proof.len(), EscrowError::ProofTooLong);
+            require!(
+                merkle::verify(proof, &root, &self.taker.key()),
+                EscrowError::TakerNotAllowed
+            );
+        }
+        Ok(())
+    }
+    // The holder gets the bond back; anyone else may only take once the window
+    // has lapsed, in which case the bond goes to the maker
+    fn settle_reservation(&self) -> Result<()> {
+        let Some(reservation) = &self.reservation else {
+            return Ok(());
+        };
+        let destination = if reservation.taker == self.taker.key() {
+            self.taker.to_account_info()
+        } else {
+            require!(
+                !reservation.is_active(Clock::get()?.slot),
+                EscrowError::EscrowReserved
+            );
+            self.maker.to_account_info()
+        };
+        reservation.close(destination)
+    }
+    fn untrack_escrow(&mut self) -> Result<()> {
+        let escrow = self.escrow.key();
+        self.registry.untrack(&escrow)
+    }
+    fn amount_owed(&self, escrow: &Escrow) -> Result<u64> {
+        let (Some(pricing), Some(price_feed)) = (escrow.oracle(), &self.price_feed) else {
+            return Ok(escrow.receive);
+        };
+        let price = oracle::parse_price(&price_feed.try_borrow_data()?)?;
+        let quote = pricing.quote(
+            &price,
+            Clock::get()?.unix_timestamp,
+            self.vault.amount,
+            self.mint_a.decimals,
+            self.mint_b.decimals,
+        )?;
+        // `receive` stays in force as the maker's floor
+        Ok(quote.max(escrow.receive))
+    }
+    fn pay(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
         transfer_checked(
             CpiContext::new(
                 self.token_program.to_account_info(),
                 TransferChecked {
                     from: self.taker_ata_b.to_account_info(),
-                    to: self.maker_ata_b.to_account_info(),
+                    to,
                     mint: self.mint_b.to_account_info(),
                     authority: self.taker.to_account_info(),
                 },
             ),
-            self.escrow.receive,
+            amount,
             self.mint_b.decimals,
-        )?;
+        )
+    }
+    // Returns the mint_b paid to the maker. Any referrer's fee is charged to
+    // the taker on top of that, and the maker can reprice an open escrow, so the
+    // taker caps the total with `max_receive`
+    fn transfer_to_maker(&mut self, escrow: &Escrow, max_receive: u64) -> Result<u64> {
+        let amount = self.amount_owed(escrow)?;
+        let fee = match &self.referrer_ata_b {
+            Some(_) => self.config.referral_fee(amount)?,
+            None => 0,
+        };
+        let total = amount.checked_add(fee).ok_or(EscrowError::MathOverflow)?;
+        require_gte!(max_receive, total, EscrowError::ReceiveAboveLimit);
+
+        self.pay(self.maker_ata_b.to_account_info(), amount)?;
+        match &self.referrer_ata_b {
+            Some(referrer_ata_b) if fee > 0 => self.pay(referrer_ata_b.to_account_info(), fee)?,
+            _ => {}
+        }
+        Ok(amount)
+    }
+    fn record_fill(&mut self, paid: u64) -> Result<()> {
+        self.maker_stats.counts.record_filled()?;
+        self.pair_stats.record_fill(self.vault.amount, paid)
+    }
+    // Vesting escrows pay into the taker's vesting vault instead, to be released
+    // over time by `claim`
+    fn start_vesting(&mut self, escrow: &Escrow, bump: Option<u8>) -> Result<()> {
+        let (Some(schedule), Some(vesting), Some(vesting_vault), Some(bump)) =
+            (escrow.vesting(), &mut self.vesting, &self.vesting_vault, bump)
+        else {
+            return Ok(());
+        };
+        // Tokens sent to the vault before it was used vest along with the
+        // proceeds, so the last claim still empties it
+        let total = self
+            .vault
+            .amount
+            .checked_add(vesting_vault.amount)
+            .ok_or(EscrowError::MathOverflow)?;
+        vesting.set_inner(Vesting {
+            escrow: self.escrow.key(),
+            beneficiary: self.taker.key(),
+            mint: self.mint_a.key(),
+            schedule,
+            start: Clock::get()?.unix_timestamp,
+            total,
+            claimed: 0,
+            bump,
+        });
         Ok(())
     }
-    fn withdraw_and_close_vault(&mut self) -> Result<()> {
+    fn proceeds_account(&self, escrow: &Escrow) -> AccountInfo<'info> {
+        match (escrow.vesting(), &self.vesting_vault) {
+            (Some(_), Some(vesting_vault)) => vesting_vault.to_account_info(),
+            _ => self.taker_ata_a.to_account_info(),
+        }
+    }
+    fn withdraw_and_close_vault(&mut self, escrow: &Escrow) -> Result<()> {
         let signer_seeds: [&[&[u8]]; 1] = [&[
             b"escrow",
-            self.maker.to_account_info().key.as_ref(),
-            &self.escrow.seed.to_le_bytes()[..],
-            &[self.escrow.bump],
+            escrow.creator.as_ref(),
+            &escrow.seed.to_le_bytes()[..],
+            &[escrow.bump],
         ]];
         transfer_checked(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
                 TransferChecked {
                     from: self.vault.to_account_info(),
-                    to: self.taker_ata_a.to_account_info(),
+                    to: self.proceeds_account(escrow),
                     mint: self.mint_a.to_account_info(),
                     authority: self.escrow.to_account_info(),
                 },
@@ -113,10 +341,53 @@ impl<'info> Take<'info> {
         ))?;
         Ok(())
     }
+    fn close_taker_account(&self, account: AccountInfo<'info>) -> Result<()> {
+        close_account(CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account,
+                authority: self.taker.to_account_info(),
+                destination: self.taker.to_account_info(),
+            },
+        ))
+    }
+    fn clean_up(&mut self, cleanup: TakeCleanup) -> Result<()> {
+        if cleanup.close_taker_ata_b {
+            self.taker_ata_b.reload()?;
+            if self.taker_ata_b.amount == 0 {
+                self.close_taker_account(self.taker_ata_b.to_account_info())?;
+            }
+        }
+        if cleanup.unwrap_sol {
+            let mint_a = self.mint_a.key();
+            require!(
+                mint_a == spl_token::native_mint::ID || mint_a == spl_token_2022::native_mint::ID,
+                EscrowError::NotWrappedSol
+            );
+            self.close_taker_account(self.taker_ata_a.to_account_info())?;
+        }
+        Ok(())
+    }
 }
 
-pub fn handler(ctx: Context<Take>) -> Result<()> {
-    ctx.accounts.transfer_to_maker()?;
-    ctx.accounts.withdraw_and_close_vault()?;
+pub fn handler(
+    ctx: Context<Take>,
+    proof: Vec<[u8; 32]>,
+    cleanup: TakeCleanup,
+    max_receive: u64,
+) -> Result<()> {
+    // Read once up front: the escrow signs the vault CPIs, so it can't stay
+    // borrowed while they run
+    let escrow = *ctx.accounts.escrow.load()?;
+    ctx.accounts.check_start_time(&escrow)?;
+    ctx.accounts.check_solo_take(&escrow)?;
+    ctx.accounts.check_allowlist(&escrow, &proof)?;
+    ctx.accounts.settle_reservation()?;
+    ctx.accounts.untrack_escrow()?;
+    let paid = ctx.accounts.transfer_to_maker(&escrow, max_receive)?;
+    ctx.accounts.record_fill(paid)?;
+    ctx.accounts.start_vesting(&escrow, ctx.bumps.vesting)?;
+    ctx.accounts.withdraw_and_close_vault(&escrow)?;
+    ctx.accounts.clean_up(cleanup)?;
     Ok(())
 }
\ No newline at end of file
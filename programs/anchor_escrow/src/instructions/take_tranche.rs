@@ -0,0 +1,192 @@
+use crate::errors::EscrowError;
+use crate::state::{Config, Escrow, MakerRegistry, MakerStats, PairStats};
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+// Fills the next tranche of a tranched escrow. The escrow stays open until the
+// tranche that empties the vault, which closes it like a regular take.
+#[derive(Accounts)]
+pub struct TakeTranche<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        seeds=[b"config"],
+        bump=config.bump,
+        constraint=!config.paused @ EscrowError::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        seeds=[b"escrow",escrow.load()?.creator.as_ref(),escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump=escrow.load()?.bump,
+        has_one=maker @ EscrowError::InvalidMaker,
+        has_one=mint_a @ EscrowError::InvalidMintA,
+        has_one=mint_b @ EscrowError::InvalidMintB,
+        constraint=escrow.load()?.tranches().is_some() @ EscrowError::NotTranched,
+        constraint=escrow.load()?.fills_freely() @ EscrowError::EscrowRestricted,
+        constraint=!escrow.load()?.trigger_pending() @ EscrowError::TriggerPending,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+    // Only shrinks on the final tranche, when the escrow is untracked
+    #[account(
+        mut,
+        seeds=[b"registry",maker.key().as_ref()],
+        bump=registry.bump,
+        realloc=MakerRegistry::space(registry.escrows.len().saturating_sub(usize::from(escrow.load()?.tranches().is_some_and(|t| t.is_last(vault.amount))))),
+        realloc::payer=maker,
+        realloc::zero=false,
+    )]
+    pub registry: Box<Account<'info, MakerRegistry>>,
+    #[account(
+        mut,
+        seeds=[b"maker_stats",maker.key().as_ref()],
+        bump=maker_stats.bump,
+    )]
+    pub maker_stats: Box<Account<'info, MakerStats>>,
+    // Every tranche adds to the volume, only the last one counts as a fill
+    #[account(
+        mut,
+        seeds=[b"pair_stats",mint_a.key().as_ref(),mint_b.key().as_ref()],
+        bump=pair_stats.bump,
+    )]
+    pub pair_stats: Box<Account<'info, PairStats>>,
+
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        address=escrow.load()?.vault(&escrow.key(), &token_program.key())?,
+        constraint=vault.amount > 0 @ EscrowError::EmptyVault,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer=taker,
+        associated_token::mint=mint_a,
+        associated_token::authority=taker,
+        associated_token::token_program=token_program,
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+       mut,
+       associated_token::mint=mint_b,
+       associated_token::authority=taker,
+       associated_token::token_program=token_program,
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer=taker,
+        associated_token::mint=mint_b,
+        associated_token::authority=maker,
+        associated_token::token_program=token_program,
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TakeTranche<'info> {
+    // The maker can reprice the escrow or its tranches while a take is in
+    // flight, so the taker caps what this tranche costs with `max_receive`
+    fn fill_tranche(&mut self, max_receive: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let escrow = *self.escrow.load()?;
+        let mut tranches = escrow.tranches().ok_or(EscrowError::NotTranched)?;
+        tranches.ensure_ready(now)?;
+
+        let (amount, price) = tranches.next_fill(self.vault.amount, escrow.receive)?;
+        require_gte!(max_receive, price, EscrowError::ReceiveAboveLimit);
+        self.transfer_to_maker(price)?;
+        self.withdraw(amount)?;
+        self.pair_stats.record_volume(amount, price)?;
+
+        tranches.record_fill(now)?;
+        let mut escrow = self.escrow.load_mut()?;
+        escrow.set_tranches(Some(tranches));
+        escrow.receive = escrow.receive.saturating_sub(price);
+        Ok(())
+    }
+    fn transfer_to_maker(&self, price: u64) -> Result<()> {
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.taker_ata_b.to_account_info(),
+                    to: self.maker_ata_b.to_account_info(),
+                    mint: self.mint_b.to_account_info(),
+                    authority: self.taker.to_account_info(),
+                },
+            ),
+            price,
+            self.mint_b.decimals,
+        )
+    }
+    fn withdraw(&self, amount: u64) -> Result<()> {
+        let escrow = *self.escrow.load()?;
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            escrow.creator.as_ref(),
+            &escrow.seed.to_le_bytes()[..],
+            &[escrow.bump],
+        ]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vault.to_account_info(),
+                    to: self.taker_ata_a.to_account_info(),
+                    mint: self.mint_a.to_account_info(),
+                    authority: self.escrow.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            amount,
+            self.mint_a.decimals,
+        )
+    }
+    fn close_if_empty(&mut self) -> Result<()> {
+        self.vault.reload()?;
+        if self.vault.amount > 0 {
+            return Ok(());
+        }
+        let escrow = self.escrow.key();
+        self.registry.untrack(&escrow)?;
+        self.maker_stats.counts.record_filled()?;
+        self.pair_stats.counts.record_filled()?;
+
+        let escrow = *self.escrow.load()?;
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            escrow.creator.as_ref(),
+            &escrow.seed.to_le_bytes()[..],
+            &[escrow.bump],
+        ]];
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault.to_account_info(),
+                authority: self.escrow.to_account_info(),
+                destination: self.maker.to_account_info(),
+            },
+            &signer_seeds,
+        ))?;
+        self.escrow.close(self.maker.to_account_info())
+    }
+}
+
+pub fn handler(ctx: Context<TakeTranche>, max_receive: u64) -> Result<()> {
+    ctx.accounts.fill_tranche(max_receive)?;
+    ctx.accounts.close_if_empty()?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::{
+    errors::EscrowError,
+    state::{Escrow, MakerRegistry, MakerStats},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct TransferEscrow<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        constraint = new_maker.key() != maker.key() @ EscrowError::InvalidMaker,
+    )]
+    pub new_maker: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+        // The order keeps naming the old maker, who alone could cancel it
+        constraint = !escrow.load()?.trigger_pending() @ EscrowError::TriggerPending,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"registry", maker.key().as_ref()],
+        bump = registry.bump,
+        realloc = MakerRegistry::space(registry.escrows.len().saturating_sub(1)),
+        realloc::payer = maker,
+        realloc::zero = false,
+    )]
+    pub registry: Box<Account<'info, MakerRegistry>>,
+
+    // Created here if the new maker has never made an escrow
+    #[account(
+        init_if_needed,
+        payer = maker,
+        space = MakerRegistry::space(0),
+        seeds = [b"registry", new_maker.key().as_ref()],
+        bump,
+    )]
+    pub new_registry: Box<Account<'info, MakerRegistry>>,
+
+    // Take needs the maker's stats, so they are created here too if missing
+    #[account(
+        init_if_needed,
+        payer = maker,
+        space = MakerStats::INIT_SPACE + MakerStats::DISCRIMINATOR.len(),
+        seeds = [b"maker_stats", new_maker.key().as_ref()],
+        bump,
+    )]
+    pub new_maker_stats: Box<Account<'info, MakerStats>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TransferEscrow<'info> {
+    fn init_new_maker_stats(&mut self, bump: u8) -> Result<()> {
+        if self.new_maker_stats.maker == Pubkey::default() {
+            self.new_maker_stats.maker = self.new_maker.key();
+            self.new_maker_stats.bump = bump;
+        }
+        Ok(())
+    }
+
+    fn hand_over(&mut self, new_registry_bump: u8) -> Result<()> {
+        // The PDA and vault stay derived from `creator`; only the rights move.
+        self.escrow.load_mut()?.maker = self.new_maker.key();
+
+        let escrow = self.escrow.key();
+        self.registry.untrack(&escrow)?;
+        if self.new_registry.maker == Pubkey::default() {
+            self.new_registry.maker = self.new_maker.key();
+            self.new_registry.bump = new_registry_bump;
+        }
+        MakerRegistry::grow(
+            &self.new_registry,
+            self.maker.to_account_info(),
+            self.system_program.to_account_info(),
+        )?;
+        self.new_registry.escrows.push(escrow);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<TransferEscrow>) -> Result<()> {
+    ctx.accounts.hand_over(ctx.bumps.new_registry)?;
+    ctx.accounts
+        .init_new_maker_stats(ctx.bumps.new_maker_stats)?;
+
+    Ok(())
+}
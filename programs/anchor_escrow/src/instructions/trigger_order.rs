@@ -0,0 +1,77 @@
+use crate::{
+    errors::EscrowError,
+    oracle,
+    state::{ConditionalOrder, Escrow},
+    trigger,
+};
+use anchor_lang::prelude::*;
+
+// Triggering is one-way: the escrow then fills at `receive` like any other,
+// even if the price later moves back across the trigger. Makers who need a
+// price check at fill time should use oracle pricing instead.
+#[derive(Accounts)]
+pub struct TriggerOrder<'info> {
+    // Permissionless: whoever lands the trigger collects the bounty
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"order", escrow.key().as_ref()],
+        bump = order.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        has_one = escrow,
+        has_one = feed @ EscrowError::InvalidPriceFeed,
+    )]
+    pub order: Account<'info, ConditionalOrder>,
+
+    /// CHECK: must be `order.feed`, parsed by `oracle::parse_price`
+    pub feed: UncheckedAccount<'info>,
+}
+
+impl<'info> TriggerOrder<'info> {
+    fn check_trigger(&self) -> Result<()> {
+        let price = oracle::parse_price(&self.feed.try_borrow_data()?)?;
+        price.ensure_usable(Clock::get()?.unix_timestamp, self.order.max_staleness)?;
+        require!(
+            trigger::crossed(
+                self.order.kind,
+                self.order.trigger_price,
+                self.order.trigger_expo,
+                &price,
+            )?,
+            EscrowError::TriggerNotReached
+        );
+        Ok(())
+    }
+    fn release_escrow(&mut self) -> Result<()> {
+        self.escrow.load_mut()?.set_trigger_pending(false);
+        Ok(())
+    }
+    fn pay_bounty(&mut self) -> Result<()> {
+        // The rest of the order's lamports go back to the maker on close
+        let bounty = self.order.bounty;
+        self.order.sub_lamports(bounty)?;
+        self.keeper.add_lamports(bounty)?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<TriggerOrder>) -> Result<()> {
+    ctx.accounts.check_trigger()?;
+    ctx.accounts.release_escrow()?;
+    ctx.accounts.pay_bounty()?;
+
+    Ok(())
+}
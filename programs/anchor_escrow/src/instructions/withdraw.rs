@@ -0,0 +1,89 @@
+use crate::{errors::EscrowError, state::Escrow};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+// Takes part of the vault back without closing the escrow, under the same
+// restrictions as `Deposit`. Withdrawing everything leaves an open escrow that
+// can't be taken until it is topped up again, or refunded.
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        has_one = mint_a @ EscrowError::InvalidMintA,
+        constraint = escrow.load()?.tranches().is_none() @ EscrowError::TranchedEscrow,
+        constraint = !escrow.load()?.reserved() @ EscrowError::EscrowReserved,
+        constraint = escrow.load()?.rfq().is_none() @ EscrowError::EscrowQuoted,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        address = escrow.load()?.vault(&escrow.key(), &token_program.key())?,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint_a,
+        token::authority = maker,
+        token::token_program = token_program,
+    )]
+    pub maker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Withdraw<'info> {
+    fn reprice(&mut self, amount: u64, receive: Option<u64>) -> Result<()> {
+        let before = self.vault.amount;
+        require_gte!(before, amount, EscrowError::InvalidAmount);
+        let mut escrow = self.escrow.load_mut()?;
+        escrow.receive = escrow.rescaled_receive(before, before - amount, receive)?;
+        Ok(())
+    }
+
+    // Signs as in `Refund::withdraw_and_close_vault`, leaving the vault open
+    fn withdraw_tokens(&mut self, amount: u64) -> Result<()> {
+        let escrow = *self.escrow.load()?;
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            escrow.creator.as_ref(),
+            &escrow.seed.to_le_bytes()[..],
+            &[escrow.bump],
+        ]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vault.to_account_info(),
+                    to: self.maker_ata_a.to_account_info(),
+                    mint: self.mint_a.to_account_info(),
+                    authority: self.escrow.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            amount,
+            self.mint_a.decimals,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<Withdraw>, amount: u64, receive: Option<u64>) -> Result<()> {
+    require_gt!(amount, 0, EscrowError::InvalidAmount);
+
+    ctx.accounts.reprice(amount, receive)?;
+    ctx.accounts.withdraw_tokens(amount)?;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::{
+    errors::EscrowError,
+    state::{Escrow, Rfq, RfqStatus},
+};
+use anchor_lang::prelude::*;
+
+// Unlinks a quote so the maker can refund or re-list the escrow. Withdrawing the
+// last quote of a cancelled RFQ closes it back to the taker.
+#[derive(Accounts)]
+pub struct WithdrawQuote<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.load()?.creator.as_ref(), escrow.load()?.seed.to_le_bytes().as_ref()],
+        bump = escrow.load()?.bump,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = escrow.load()?.rfq() == Some(rfq.key()) @ EscrowError::QuoteNotFound,
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"rfq", rfq.taker.as_ref(), rfq.seed.to_le_bytes().as_ref()],
+        bump = rfq.bump,
+        has_one = taker,
+    )]
+    pub rfq: Account<'info, Rfq>,
+
+    #[account(mut)]
+    pub taker: SystemAccount<'info>,
+}
+
+impl<'info> WithdrawQuote<'info> {
+    fn unlink_quote(&mut self) -> Result<()> {
+        let escrow = self.escrow.key();
+        self.rfq.unlink(&escrow)?;
+        self.escrow.load_mut()?.set_rfq(None);
+
+        if self.rfq.status == RfqStatus::Cancelled && self.rfq.quotes.is_empty() {
+            self.rfq.close(self.taker.to_account_info())?;
+        }
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<WithdrawQuote>) -> Result<()> {
+    ctx.accounts.unlink_quote()?;
+
+    Ok(())
+}
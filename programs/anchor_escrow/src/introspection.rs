@@ -0,0 +1,37 @@
+use crate::errors::EscrowError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+    sysvar::instructions::load_instruction_at_checked,
+};
+
+// Guards a solo take: the transaction may hold nothing but this program's own
+// instructions and compute budget settings, which can't move a market, and
+// take must be called directly rather than through CPI.
+
+const COMPUTE_BUDGET_ID: Pubkey =
+    Pubkey::from_str_const("ComputeBudget111111111111111111111111111111");
+
+// The sysvar opens with a little-endian u16 count of the transaction's
+// top-level instructions
+fn instruction_count(instructions_sysvar: &AccountInfo) -> Result<usize> {
+    let data = instructions_sysvar.try_borrow_data()?;
+    let count = data.get(..2).ok_or(ProgramError::InvalidAccountData)?;
+    Ok(u16::from_le_bytes([count[0], count[1]]).into())
+}
+
+pub fn ensure_alone(instructions_sysvar: &AccountInfo) -> Result<()> {
+    require_eq!(
+        get_stack_height(),
+        TRANSACTION_LEVEL_STACK_HEIGHT,
+        EscrowError::TakeViaCpi
+    );
+    for index in 0..instruction_count(instructions_sysvar)? {
+        let program_id = load_instruction_at_checked(index, instructions_sysvar)?.program_id;
+        require!(
+            program_id == crate::ID || program_id == COMPUTE_BUDGET_ID,
+            EscrowError::TakeNotAlone
+        );
+    }
+    Ok(())
+}
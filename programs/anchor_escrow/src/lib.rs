@@ -3,21 +3,221 @@
 use anchor_lang::prelude::*;
 pub mod errors;
 pub mod instructions;
+pub mod introspection;
+pub mod merkle;
+pub mod migration;
+pub mod oracle;
 pub use instructions::*;
 pub mod state;
+pub mod tranche;
+pub mod trigger;
+pub mod vesting;
 
 declare_id!("8LTee82TkoqBoBjBAz2yAAKSj9ckr7zz5vMi6rJQTwhJ");
 
 #[program]
 pub mod anchor_escrow {
     use super::*;
+    pub fn init_registry(ctx: Context<InitRegistry>) -> Result<()> {
+        instructions::init_registry::handler(ctx)
+    }
     pub fn make(ctx: Context<Make>, seed: u64, receive: u64, amount: u64) -> Result<()> {
-        instructions::make::handler(ctx, seed, receive, amount)
+        instructions::make::handler(ctx, seed, receive, amount, state::VaultKind::Ata)
+    }
+    pub fn make_with_pda_vault(
+        ctx: Context<Make>,
+        seed: u64,
+        receive: u64,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::make::handler(ctx, seed, receive, amount, state::VaultKind::Pda)
     }
-    pub fn take(ctx: Context<Take>) -> Result<()> {
-        instructions::take::handler(ctx)
+    pub fn take(
+        ctx: Context<Take>,
+        proof: Vec<[u8; 32]>,
+        cleanup: TakeCleanup,
+        max_receive: u64,
+    ) -> Result<()> {
+        instructions::take::handler(ctx, proof, cleanup, max_receive)
     }
     pub fn refund(ctx: Context<Refund>) -> Result<()> {
         instructions::refund::handler(ctx)
     }
+    pub fn transfer_escrow(ctx: Context<TransferEscrow>) -> Result<()> {
+        instructions::transfer_escrow::handler(ctx)
+    }
+    pub fn refund_all<'info>(ctx: Context<'_, '_, 'info, 'info, RefundAll<'info>>) -> Result<()> {
+        instructions::refund_all::handler(ctx)
+    }
+    pub fn set_allowlist(ctx: Context<SetAllowlist>, root: Option<[u8; 32]>) -> Result<()> {
+        instructions::set_allowlist::handler(ctx, root)
+    }
+    pub fn set_gate(
+        ctx: Context<SetGate>,
+        gate_mint: Option<Pubkey>,
+        min_gate_balance: u64,
+    ) -> Result<()> {
+        instructions::set_gate::handler(ctx, gate_mint, min_gate_balance)
+    }
+    pub fn set_oracle_pricing(
+        ctx: Context<SetOraclePricing>,
+        pricing: Option<oracle::OraclePricing>,
+    ) -> Result<()> {
+        instructions::set_oracle_pricing::handler(ctx, pricing)
+    }
+    pub fn set_vesting(
+        ctx: Context<SetVesting>,
+        schedule: Option<vesting::VestingSchedule>,
+    ) -> Result<()> {
+        instructions::set_vesting::handler(ctx, schedule)
+    }
+    pub fn set_tranches(
+        ctx: Context<SetTranches>,
+        schedule: Option<tranche::TrancheSchedule>,
+    ) -> Result<()> {
+        instructions::set_tranches::handler(ctx, schedule)
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_order(
+        ctx: Context<PlaceOrder>,
+        feed: Pubkey,
+        kind: trigger::OrderKind,
+        trigger_price: i64,
+        trigger_expo: i32,
+        max_staleness: u64,
+        bounty: u64,
+    ) -> Result<()> {
+        instructions::place_order::handler(
+            ctx,
+            feed,
+            kind,
+            trigger_price,
+            trigger_expo,
+            max_staleness,
+            bounty,
+        )
+    }
+    pub fn trigger_order(ctx: Context<TriggerOrder>) -> Result<()> {
+        instructions::trigger_order::handler(ctx)
+    }
+    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+        instructions::cancel_order::handler(ctx)
+    }
+    pub fn match_orders(ctx: Context<MatchOrders>) -> Result<()> {
+        instructions::match_orders::handler(ctx)
+    }
+    pub fn flash_take<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FlashTake<'info>>,
+        callback_data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::flash_take::handler(ctx, callback_data)
+    }
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        instructions::claim::handler(ctx)
+    }
+    pub fn take_tranche(ctx: Context<TakeTranche>, max_receive: u64) -> Result<()> {
+        instructions::take_tranche::handler(ctx, max_receive)
+    }
+    pub fn set_reservation_terms(
+        ctx: Context<SetReservationTerms>,
+        terms: Option<state::ReservationTerms>,
+    ) -> Result<()> {
+        instructions::set_reservation_terms::handler(ctx, terms)
+    }
+    pub fn reserve(ctx: Context<Reserve>) -> Result<()> {
+        instructions::reserve::handler(ctx)
+    }
+    pub fn forfeit_reservation(ctx: Context<ForfeitReservation>) -> Result<()> {
+        instructions::forfeit_reservation::handler(ctx)
+    }
+    pub fn open_rfq(ctx: Context<OpenRfq>, seed: u64, amount: u64) -> Result<()> {
+        instructions::open_rfq::handler(ctx, seed, amount)
+    }
+    pub fn submit_quote(ctx: Context<SubmitQuote>) -> Result<()> {
+        instructions::submit_quote::handler(ctx)
+    }
+    pub fn withdraw_quote(ctx: Context<WithdrawQuote>) -> Result<()> {
+        instructions::withdraw_quote::handler(ctx)
+    }
+    pub fn cancel_rfq(ctx: Context<CancelRfq>) -> Result<()> {
+        instructions::cancel_rfq::handler(ctx)
+    }
+    pub fn accept_quote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AcceptQuote<'info>>,
+    ) -> Result<()> {
+        instructions::accept_quote::handler(ctx)
+    }
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        seed: u64,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::create_multisig::handler(ctx, seed, approvers, threshold)
+    }
+    pub fn propose_make(
+        ctx: Context<ProposeMake>,
+        seed: u64,
+        receive: u64,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::propose_make::handler(ctx, seed, receive, amount)
+    }
+    pub fn propose_refund(ctx: Context<ProposeRefund>) -> Result<()> {
+        instructions::propose_refund::handler(ctx)
+    }
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {
+        instructions::approve::handler(ctx)
+    }
+    pub fn execute_make(ctx: Context<ExecuteMake>) -> Result<()> {
+        instructions::execute_make::handler(ctx)
+    }
+    pub fn execute_refund(ctx: Context<ExecuteRefund>) -> Result<()> {
+        instructions::execute_refund::handler(ctx)
+    }
+    pub fn set_solo_take(ctx: Context<SetSoloTake>, enabled: bool) -> Result<()> {
+        instructions::set_solo_take::handler(ctx, enabled)
+    }
+    pub fn migrate_escrow(ctx: Context<MigrateEscrow>) -> Result<()> {
+        instructions::migrate_escrow::handler(ctx)
+    }
+    pub fn init_config(ctx: Context<InitConfig>) -> Result<()> {
+        instructions::init_config::handler(ctx)
+    }
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        instructions::set_paused::handler(ctx, paused)
+    }
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+        instructions::propose_admin::handler(ctx, new_admin)
+    }
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+    pub fn set_mint_rule(ctx: Context<SetMintRule>, listing: state::MintListing) -> Result<()> {
+        instructions::set_mint_rule::handler(ctx, listing)
+    }
+    pub fn remove_mint_rule(ctx: Context<RemoveMintRule>) -> Result<()> {
+        instructions::remove_mint_rule::handler(ctx)
+    }
+    pub fn set_allowlist_only(ctx: Context<SetAllowlistOnly>, enabled: bool) -> Result<()> {
+        instructions::set_allowlist_only::handler(ctx, enabled)
+    }
+    pub fn set_referral_bps(ctx: Context<SetReferralBps>, referral_bps: u16) -> Result<()> {
+        instructions::set_referral_bps::handler(ctx, referral_bps)
+    }
+    pub fn deposit(ctx: Context<Deposit>, amount: u64, receive: Option<u64>) -> Result<()> {
+        instructions::deposit::handler(ctx, amount, receive)
+    }
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64, receive: Option<u64>) -> Result<()> {
+        instructions::withdraw::handler(ctx, amount, receive)
+    }
+    pub fn set_start_time(ctx: Context<SetStartTime>, start: Option<state::StartTime>) -> Result<()> {
+        instructions::set_start_time::handler(ctx, start)
+    }
+    pub fn set_oracle_program(
+        ctx: Context<SetOracleProgram>,
+        oracle_program: Pubkey,
+    ) -> Result<()> {
+        instructions::set_oracle_program::handler(ctx, oracle_program)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+// sha256 Merkle tree over taker pubkeys. Leaves and inner nodes are hashed with
+// distinct prefixes so an inner node can never be passed off as a leaf, and
+// siblings are sorted before hashing so proofs don't need direction bits.
+
+// Enough for 65,536 takers while keeping `take` inside the transaction size limit
+pub const MAX_PROOF_LEN: usize = 16;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf(key: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, key.as_ref()]).to_bytes()
+}
+
+pub fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], key: &Pubkey) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf(key), |hash, sibling| node(&hash, sibling));
+    computed == *root
+}
@@ -0,0 +1,73 @@
+use crate::{errors::EscrowError, state::Escrow};
+use anchor_lang::prelude::*;
+
+// Escrow account layouts. v1 is the original Borsh account under
+// `LEGACY_DISCRIMINATOR`, which had no version field. v2 is zero-copy, got its
+// own discriminator so that a v1 account can never be loaded as one, and stores
+// `version` right after it so later layouts can be told apart before decoding.
+
+pub const ESCROW_VERSION: u8 = 2;
+
+pub const LEGACY_DISCRIMINATOR: &[u8] = &[1];
+
+// The v1 layout, still held by escrows made before versioning
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EscrowV1 {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub receive: u64,
+    pub bump: u8,
+}
+
+impl EscrowV1 {
+    // Account size including the discriminator
+    pub const LEN: usize = LEGACY_DISCRIMINATOR.len() + 8 + 32 * 3 + 8 + 1;
+
+    // The escrow as it reads in the current layout, with every later feature
+    // switched off
+    pub fn upgrade(self) -> Escrow {
+        Escrow::new(
+            self.seed,
+            self.maker,
+            self.mint_a,
+            self.mint_b,
+            self.receive,
+            self.bump,
+            0,
+        )
+    }
+}
+
+pub fn escrow_version(data: &[u8]) -> Result<u8> {
+    if data.starts_with(LEGACY_DISCRIMINATOR) {
+        require_eq!(
+            data.len(),
+            EscrowV1::LEN,
+            ErrorCode::AccountDidNotDeserialize
+        );
+        return Ok(1);
+    }
+    require!(
+        data.starts_with(Escrow::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    data.get(Escrow::DISCRIMINATOR.len())
+        .copied()
+        .ok_or(ErrorCode::AccountDidNotDeserialize.into())
+}
+
+// Reads an escrow of any supported version into the current layout. v1 didn't
+// cache the vault bump, so its is left zero for the caller to look up.
+pub fn decode_escrow(data: &[u8]) -> Result<Escrow> {
+    match escrow_version(data)? {
+        1 => {
+            // `escrow_version` has checked the length, so this can't run short
+            let mut body = &data[LEGACY_DISCRIMINATOR.len()..];
+            Ok(EscrowV1::deserialize(&mut body)?.upgrade())
+        }
+        ESCROW_VERSION => Escrow::try_deserialize(&mut &data[..]),
+        _ => err!(EscrowError::UnsupportedEscrowVersion),
+    }
+}
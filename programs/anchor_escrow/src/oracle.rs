@@ -0,0 +1,127 @@
+use crate::errors::EscrowError;
+use anchor_lang::prelude::*;
+
+// Price accounts follow the Pyth v2 price account layout. Only the fields read
+// below are required; everything after the aggregate price is ignored, so a
+// 240-byte account is a valid feed.
+//
+//   offset  size  field
+//   0       4     magic       u32, 0xa1b2c3d4
+//   4       4     version     u32, 2
+//   8       4     atype       u32, 3 (price account)
+//   20      4     expo        i32
+//   96      8     timestamp   i64, unix time of the aggregate
+//   208     8     agg.price   i64
+//   216     8     agg.conf    u64
+//   224     4     agg.status  u32, 1 (trading)
+//
+// All integers are little-endian. Nothing here proves who wrote the bytes, so
+// take also checks the feed's owner against `Config::oracle_program`.
+
+pub const MAGIC: u32 = 0xa1b2_c3d4;
+pub const VERSION: u32 = 2;
+pub const ACCOUNT_TYPE_PRICE: u32 = 3;
+pub const STATUS_TRADING: u32 = 1;
+pub const PRICE_ACCOUNT_LEN: usize = 240;
+
+const BPS: u128 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Price {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePricing {
+    // Price feed quoting one whole mint_a token in whole mint_b tokens
+    pub feed: Pubkey,
+    // Premium the taker pays on top of the oracle price
+    pub spread_bps: u16,
+    // Oldest aggregate, in seconds, that take will still accept
+    pub max_staleness: u64,
+    // Widest confidence interval accepted, relative to the price
+    pub max_confidence_bps: u16,
+}
+
+fn read<const N: usize>(data: &[u8], offset: usize) -> [u8; N] {
+    data[offset..offset + N].try_into().unwrap()
+}
+
+pub fn parse_price(data: &[u8]) -> Result<Price> {
+    require_gte!(data.len(), PRICE_ACCOUNT_LEN, EscrowError::InvalidPriceFeed);
+    require!(
+        u32::from_le_bytes(read(data, 0)) == MAGIC
+            && u32::from_le_bytes(read(data, 4)) == VERSION
+            && u32::from_le_bytes(read(data, 8)) == ACCOUNT_TYPE_PRICE,
+        EscrowError::InvalidPriceFeed
+    );
+    require_eq!(
+        u32::from_le_bytes(read(data, 224)),
+        STATUS_TRADING,
+        EscrowError::InvalidPriceFeed
+    );
+
+    Ok(Price {
+        price: i64::from_le_bytes(read(data, 208)),
+        conf: u64::from_le_bytes(read(data, 216)),
+        expo: i32::from_le_bytes(read(data, 20)),
+        timestamp: i64::from_le_bytes(read(data, 96)),
+    })
+}
+
+impl Price {
+    pub fn ensure_usable(&self, now: i64, max_staleness: u64) -> Result<()> {
+        require!(
+            now.saturating_sub(self.timestamp) <= max_staleness as i64,
+            EscrowError::StalePrice
+        );
+        require_gt!(self.price, 0, EscrowError::NegativePrice);
+        Ok(())
+    }
+}
+
+impl OraclePricing {
+    // Amount of mint_b, in base units, owed for `amount_a` base units of mint_a
+    pub fn quote(
+        &self,
+        price: &Price,
+        now: i64,
+        amount_a: u64,
+        decimals_a: u8,
+        decimals_b: u8,
+    ) -> Result<u64> {
+        price.ensure_usable(now, self.max_staleness)?;
+        let value = price.price as u128;
+        require!(
+            (price.conf as u128) * BPS <= value * self.max_confidence_bps as u128,
+            EscrowError::PriceConfidenceTooWide
+        );
+
+        let mut numerator = (amount_a as u128)
+            .checked_mul(value)
+            .and_then(|n| n.checked_mul(BPS + self.spread_bps as u128))
+            .ok_or(EscrowError::MathOverflow)?;
+        let mut denominator = BPS;
+
+        let scale = price.expo + decimals_b as i32 - decimals_a as i32;
+        let factor = 10u128
+            .checked_pow(scale.unsigned_abs())
+            .ok_or(EscrowError::MathOverflow)?;
+        if scale >= 0 {
+            numerator = numerator
+                .checked_mul(factor)
+                .ok_or(EscrowError::MathOverflow)?;
+        } else {
+            denominator = denominator
+                .checked_mul(factor)
+                .ok_or(EscrowError::MathOverflow)?;
+        }
+
+        // Round in the maker's favour
+        let amount_b = numerator.div_ceil(denominator);
+        u64::try_from(amount_b).map_err(|_| error!(EscrowError::MathOverflow))
+    }
+}
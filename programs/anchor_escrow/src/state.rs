@@ -1,12 +1,712 @@
+use crate::errors::EscrowError;
+use crate::migration::ESCROW_VERSION;
+use crate::oracle::OraclePricing;
+use crate::tranche::{TrancheSchedule, Tranches};
+use crate::trigger::OrderKind;
+use crate::vesting::VestingSchedule;
 use anchor_lang::prelude::*;
  
-#[derive(InitSpace)]
-#[account(discriminator = 1)]
-  pub struct Escrow {
+// Zero-copy so that instructions read and write the fields in place rather
+// than deserializing the whole account. The layout is packed behind the
+// discriminator, so every field is a plain value: optional settings keep their
+// payload zeroed while off and are flagged in `flags`, and are read and written
+// through the accessors below.
+#[account(zero_copy(unsafe), discriminator = 11)]
+#[derive(Debug, PartialEq, Eq)]
+pub struct Escrow {
+  // Layout version, see `migration`
+  pub version: u8,
   pub seed: u64,
   pub maker: Pubkey,
+  pub creator: Pubkey,
   pub mint_a: Pubkey,
   pub mint_b: Pubkey,
   pub receive: u64,
   pub bump: u8,
+  // Bump of `vault`, so it is checked without searching for it again
+  pub vault_bump: u8,
+  pub min_gate_balance: u64,
+  flags: u16,
+  allowlist_root: [u8; 32],
+  gate_mint: Pubkey,
+  oracle_feed: Pubkey,
+  oracle_spread_bps: u16,
+  oracle_max_staleness: u64,
+  oracle_max_confidence_bps: u16,
+  vesting_cliff: i64,
+  vesting_duration: i64,
+  tranche_size: u64,
+  tranche_interval: i64,
+  // Zero when tranches are priced pro rata, which `validate` rules out as a price
+  tranche_receive: u64,
+  tranche_index: u32,
+  tranche_last_fill: i64,
+  reservation_bond: u64,
+  reservation_window: u64,
+  rfq: Pubkey,
+  start_time: i64,
+  start_slot: u64,
+}
+
+const ALLOWLIST: u16 = 1 << 0;
+const GATE: u16 = 1 << 1;
+const ORACLE: u16 = 1 << 2;
+const VESTING: u16 = 1 << 3;
+const TRANCHES: u16 = 1 << 4;
+const RESERVATION_TERMS: u16 = 1 << 5;
+const RFQ: u16 = 1 << 6;
+const TRIGGER_PENDING: u16 = 1 << 7;
+const RESERVED: u16 = 1 << 8;
+// Take must run alone in its transaction, see `introspection`
+const SOLO_TAKE: u16 = 1 << 9;
+// The vault is a token account at `[b"vault", escrow]` rather than an ATA
+const PDA_VAULT: u16 = 1 << 10;
+const START_TIME: u16 = 1 << 11;
+// The start is `start_slot` rather than `start_time`
+const START_AT_SLOT: u16 = 1 << 12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VaultKind {
+  // The escrow's mint_a ATA
+  Ata,
+  // A token account at the program PDA `[b"vault", escrow]`, created directly
+  // through the token program
+  Pda,
+}
+
+// When an escrow opens to takes, by either clock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StartTime {
+  UnixTimestamp(i64),
+  Slot(u64),
+}
+
+impl StartTime {
+  pub fn has_passed(&self, clock: &Clock) -> bool {
+    match *self {
+      StartTime::UnixTimestamp(start) => clock.unix_timestamp >= start,
+      StartTime::Slot(start) => clock.slot >= start,
+    }
+  }
+}
+
+impl Escrow {
+  // Account size including the discriminator
+  pub const SPACE: usize = Escrow::DISCRIMINATOR.len() + std::mem::size_of::<Escrow>();
+
+  // A fresh escrow with every optional feature switched off
+  pub fn new(
+    seed: u64,
+    maker: Pubkey,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    receive: u64,
+    bump: u8,
+    vault_bump: u8,
+  ) -> Self {
+    Self {
+      version: ESCROW_VERSION,
+      seed,
+      maker,
+      creator: maker,
+      mint_a,
+      mint_b,
+      receive,
+      bump,
+      vault_bump,
+      min_gate_balance: 0,
+      flags: 0,
+      allowlist_root: [0; 32],
+      gate_mint: Pubkey::default(),
+      oracle_feed: Pubkey::default(),
+      oracle_spread_bps: 0,
+      oracle_max_staleness: 0,
+      oracle_max_confidence_bps: 0,
+      vesting_cliff: 0,
+      vesting_duration: 0,
+      tranche_size: 0,
+      tranche_interval: 0,
+      tranche_receive: 0,
+      tranche_index: 0,
+      tranche_last_fill: 0,
+      reservation_bond: 0,
+      reservation_window: 0,
+      rfq: Pubkey::default(),
+      start_time: 0,
+      start_slot: 0,
+    }
+  }
+
+  // The escrow's vault of either kind, derived from the cached bump
+  pub fn vault(&self, escrow: &Pubkey, token_program: &Pubkey) -> Result<Pubkey> {
+    match self.vault_kind() {
+      VaultKind::Ata => Pubkey::create_program_address(
+        &[
+          escrow.as_ref(),
+          token_program.as_ref(),
+          self.mint_a.as_ref(),
+          &[self.vault_bump],
+        ],
+        &anchor_spl::associated_token::ID,
+      )
+      .map_err(|_| error!(ErrorCode::ConstraintAssociated)),
+      VaultKind::Pda => Pubkey::create_program_address(
+        &[b"vault", escrow.as_ref(), &[self.vault_bump]],
+        &crate::ID,
+      )
+      .map_err(|_| error!(ErrorCode::ConstraintSeeds)),
+    }
+  }
+
+  // Whether any of `flags` is set
+  fn has(&self, flags: u16) -> bool {
+    self.flags & flags != 0
+  }
+
+  fn set_flag(&mut self, flag: u16, on: bool) {
+    if on {
+      self.flags |= flag;
+    } else {
+      self.flags &= !flag;
+    }
+  }
+
+  pub fn allowlist_root(&self) -> Option<[u8; 32]> {
+    self.has(ALLOWLIST).then_some(self.allowlist_root)
+  }
+
+  pub fn set_allowlist_root(&mut self, root: Option<[u8; 32]>) {
+    self.set_flag(ALLOWLIST, root.is_some());
+    self.allowlist_root = root.unwrap_or_default();
+  }
+
+  pub fn gate_mint(&self) -> Option<Pubkey> {
+    self.has(GATE).then_some(self.gate_mint)
+  }
+
+  pub fn set_gate_mint(&mut self, gate_mint: Option<Pubkey>) {
+    self.set_flag(GATE, gate_mint.is_some());
+    self.gate_mint = gate_mint.unwrap_or_default();
+  }
+
+  pub fn oracle(&self) -> Option<OraclePricing> {
+    self.has(ORACLE).then_some(OraclePricing {
+      feed: self.oracle_feed,
+      spread_bps: self.oracle_spread_bps,
+      max_staleness: self.oracle_max_staleness,
+      max_confidence_bps: self.oracle_max_confidence_bps,
+    })
+  }
+
+  pub fn set_oracle(&mut self, pricing: Option<OraclePricing>) {
+    self.set_flag(ORACLE, pricing.is_some());
+    self.oracle_feed = pricing.map_or_else(Pubkey::default, |p| p.feed);
+    self.oracle_spread_bps = pricing.map_or(0, |p| p.spread_bps);
+    self.oracle_max_staleness = pricing.map_or(0, |p| p.max_staleness);
+    self.oracle_max_confidence_bps = pricing.map_or(0, |p| p.max_confidence_bps);
+  }
+
+  pub fn vesting(&self) -> Option<VestingSchedule> {
+    self.has(VESTING).then_some(VestingSchedule {
+      cliff: self.vesting_cliff,
+      duration: self.vesting_duration,
+    })
+  }
+
+  pub fn set_vesting(&mut self, schedule: Option<VestingSchedule>) {
+    self.set_flag(VESTING, schedule.is_some());
+    self.vesting_cliff = schedule.map_or(0, |s| s.cliff);
+    self.vesting_duration = schedule.map_or(0, |s| s.duration);
+  }
+
+  pub fn tranches(&self) -> Option<Tranches> {
+    self.has(TRANCHES).then(|| Tranches {
+      schedule: TrancheSchedule {
+        size: self.tranche_size,
+        interval: self.tranche_interval,
+        receive: (self.tranche_receive > 0).then_some(self.tranche_receive),
+      },
+      index: self.tranche_index,
+      last_fill: self.tranche_last_fill,
+    })
+  }
+
+  pub fn set_tranches(&mut self, tranches: Option<Tranches>) {
+    self.set_flag(TRANCHES, tranches.is_some());
+    self.tranche_size = tranches.map_or(0, |t| t.schedule.size);
+    self.tranche_interval = tranches.map_or(0, |t| t.schedule.interval);
+    self.tranche_receive = tranches.and_then(|t| t.schedule.receive).unwrap_or(0);
+    self.tranche_index = tranches.map_or(0, |t| t.index);
+    self.tranche_last_fill = tranches.map_or(0, |t| t.last_fill);
+  }
+
+  pub fn reservation_terms(&self) -> Option<ReservationTerms> {
+    self.has(RESERVATION_TERMS).then_some(ReservationTerms {
+      bond: self.reservation_bond,
+      window: self.reservation_window,
+    })
+  }
+
+  pub fn set_reservation_terms(&mut self, terms: Option<ReservationTerms>) {
+    self.set_flag(RESERVATION_TERMS, terms.is_some());
+    self.reservation_bond = terms.map_or(0, |t| t.bond);
+    self.reservation_window = terms.map_or(0, |t| t.window);
+  }
+
+  pub fn rfq(&self) -> Option<Pubkey> {
+    self.has(RFQ).then_some(self.rfq)
+  }
+
+  pub fn set_rfq(&mut self, rfq: Option<Pubkey>) {
+    self.set_flag(RFQ, rfq.is_some());
+    self.rfq = rfq.unwrap_or_default();
+  }
+
+  pub fn trigger_pending(&self) -> bool {
+    self.has(TRIGGER_PENDING)
+  }
+
+  pub fn set_trigger_pending(&mut self, pending: bool) {
+    self.set_flag(TRIGGER_PENDING, pending);
+  }
+
+  pub fn reserved(&self) -> bool {
+    self.has(RESERVED)
+  }
+
+  pub fn set_reserved(&mut self, reserved: bool) {
+    self.set_flag(RESERVED, reserved);
+  }
+
+  pub fn solo_take(&self) -> bool {
+    self.has(SOLO_TAKE)
+  }
+
+  pub fn set_solo_take(&mut self, enabled: bool) {
+    self.set_flag(SOLO_TAKE, enabled);
+  }
+
+  pub fn vault_kind(&self) -> VaultKind {
+    if self.has(PDA_VAULT) {
+      VaultKind::Pda
+    } else {
+      VaultKind::Ata
+    }
+  }
+
+  pub fn set_vault_kind(&mut self, kind: VaultKind) {
+    self.set_flag(PDA_VAULT, kind == VaultKind::Pda);
+  }
+
+  pub fn start_time(&self) -> Option<StartTime> {
+    match (self.has(START_TIME), self.has(START_AT_SLOT)) {
+      (false, _) => None,
+      (true, false) => Some(StartTime::UnixTimestamp(self.start_time)),
+      (true, true) => Some(StartTime::Slot(self.start_slot)),
+    }
+  }
+
+  pub fn set_start_time(&mut self, start: Option<StartTime>) {
+    self.set_flag(START_TIME, start.is_some());
+    self.set_flag(START_AT_SLOT, matches!(start, Some(StartTime::Slot(_))));
+    self.start_time = match start {
+      Some(StartTime::UnixTimestamp(start)) => start,
+      _ => 0,
+    };
+    self.start_slot = match start {
+      Some(StartTime::Slot(start)) => start,
+      _ => 0,
+    };
+  }
+
+  // The price after the vault goes from `before` to `after`: `explicit` if
+  // given, else `receive` scaled in proportion and rounded up in the maker's
+  // favour. An emptied vault keeps its old price, and one refilled from empty
+  // has nothing to scale from, so it needs an explicit price.
+  pub fn rescaled_receive(&self, before: u64, after: u64, explicit: Option<u64>) -> Result<u64> {
+    if let Some(receive) = explicit {
+      require_gt!(receive, 0, EscrowError::InvalidAmount);
+      return Ok(receive);
+    }
+    if after == 0 {
+      return Ok(self.receive);
+    }
+    require_gt!(before, 0, EscrowError::ReceiveRequired);
+    let scaled = (u128::from(self.receive) * u128::from(after)).div_ceil(u128::from(before));
+    u64::try_from(scaled).map_err(|_| error!(EscrowError::MathOverflow))
+  }
+
+  // Open to any taker at a fixed price, paying out immediately, with no live
+  // reservation, not quoted into an RFQ, fillable alongside other instructions
+  // and with no start time to wait for
+  pub fn fills_freely(&self) -> bool {
+    !self.has(ALLOWLIST | GATE | ORACLE | VESTING | RESERVED | RFQ | SOLO_TAKE | START_TIME)
+  }
+
+  // Fills that bypass take (matching, flash takes) swap the whole vault at once,
+  // so they also rule out tranches
+  pub fn is_unrestricted(&self) -> bool {
+    self.fills_freely() && !self.has(TRANCHES)
+  }
+}
+
+#[account(discriminator = 2)]
+pub struct MakerRegistry {
+  pub maker: Pubkey,
+  pub bump: u8,
+  pub escrows: Vec<Pubkey>,
+}
+
+impl MakerRegistry {
+  pub fn space(len: usize) -> usize {
+    MakerRegistry::DISCRIMINATOR.len() + 32 + 1 + 4 + len * 32
+  }
+
+  // Makes room for one more escrow, with `payer` topping the rent up. For
+  // instructions that may have just created the registry, since `realloc`
+  // can't be combined with `init_if_needed`.
+  pub fn grow<'info>(
+    registry: &Account<'info, MakerRegistry>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+  ) -> Result<()> {
+    let info = registry.to_account_info();
+    let space = MakerRegistry::space(registry.escrows.len() + 1);
+    let shortfall = Rent::get()?.minimum_balance(space).saturating_sub(info.lamports());
+    if shortfall > 0 {
+      anchor_lang::system_program::transfer(
+        CpiContext::new(
+          system_program,
+          anchor_lang::system_program::Transfer { from: payer, to: info.clone() },
+        ),
+        shortfall,
+      )?;
+    }
+    info.resize(space)?;
+    Ok(())
+  }
+
+  pub fn tracks(&self, escrow: &Pubkey) -> bool {
+    self.escrows.contains(escrow)
+  }
+
+  pub fn untrack(&mut self, escrow: &Pubkey) -> Result<()> {
+    let index = self
+      .escrows
+      .iter()
+      .position(|key| key == escrow)
+      .ok_or(EscrowError::EscrowNotRegistered)?;
+    self.escrows.swap_remove(index);
+    Ok(())
+  }
+}
+
+#[derive(InitSpace)]
+#[account(discriminator = 3)]
+pub struct ConditionalOrder {
+  pub escrow: Pubkey,
+  pub maker: Pubkey,
+  pub feed: Pubkey,
+  pub kind: OrderKind,
+  pub trigger_price: i64,
+  pub trigger_expo: i32,
+  pub max_staleness: u64,
+  pub bounty: u64,
+  pub bump: u8,
+}
+
+#[derive(InitSpace)]
+#[account(discriminator = 4)]
+pub struct Vesting {
+  pub escrow: Pubkey,
+  pub beneficiary: Pubkey,
+  pub mint: Pubkey,
+  pub schedule: VestingSchedule,
+  pub start: i64,
+  pub total: u64,
+  pub claimed: u64,
+  pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReservationTerms {
+  // Lamports a taker locks up to reserve the escrow
+  pub bond: u64,
+  // Slots the escrow stays exclusive to that taker
+  pub window: u64,
+}
+
+#[derive(InitSpace)]
+#[account(discriminator = 5)]
+pub struct Reservation {
+  pub escrow: Pubkey,
+  pub taker: Pubkey,
+  pub bond: u64,
+  pub expires_slot: u64,
+  pub bump: u8,
+}
+
+impl Reservation {
+  pub fn is_active(&self, slot: u64) -> bool {
+    slot <= self.expires_slot
+  }
+}
+
+// Quotes an RFQ holds at once, bounded by how many losing quotes accept_quote
+// can refund within one transaction
+pub const MAX_QUOTES: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RfqStatus {
+  // Takes new quotes and can be accepted
+  Open,
+  // Closes once the last quote has been withdrawn
+  Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quote {
+  pub escrow: Pubkey,
+  pub maker: Pubkey,
+}
+
+#[derive(InitSpace)]
+#[account(discriminator = 6)]
+pub struct Rfq {
+  pub taker: Pubkey,
+  pub seed: u64,
+  // Mint the taker wants and how much of it
+  pub mint_a: Pubkey,
+  pub amount: u64,
+  // Mint the taker pays in
+  pub mint_b: Pubkey,
+  pub status: RfqStatus,
+  #[max_len(MAX_QUOTES)]
+  pub quotes: Vec<Quote>,
+  pub bump: u8,
+}
+
+impl Rfq {
+  pub fn unlink(&mut self, escrow: &Pubkey) -> Result<()> {
+    let index = self
+      .quotes
+      .iter()
+      .position(|quote| quote.escrow == *escrow)
+      .ok_or(EscrowError::QuoteNotFound)?;
+    self.quotes.remove(index);
+    Ok(())
+  }
+}
+
+// Largest approver set a multisig maker can have
+pub const MAX_APPROVERS: usize = 10;
+
+// A maker that is an M-of-N set of approvers. Escrows are made and refunded by
+// its signer PDA, `[b"multisig_signer", multisig]`, which also holds the SOL
+// for rent.
+#[derive(InitSpace)]
+#[account(discriminator = 7)]
+pub struct Multisig {
+  pub creator: Pubkey,
+  pub seed: u64,
+  #[max_len(MAX_APPROVERS)]
+  pub approvers: Vec<Pubkey>,
+  pub threshold: u8,
+  pub proposal_count: u64,
+  pub bump: u8,
+  pub signer_bump: u8,
+}
+
+impl Multisig {
+  pub fn ensure_approver(&self, key: &Pubkey) -> Result<()> {
+    require!(self.approvers.contains(key), EscrowError::NotAnApprover);
+    Ok(())
+  }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultisigAction {
+  Make {
+    seed: u64,
+    receive: u64,
+    amount: u64,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+  },
+  Refund {
+    escrow: Pubkey,
+  },
+}
+
+#[derive(InitSpace)]
+#[account(discriminator = 8)]
+pub struct Proposal {
+  pub multisig: Pubkey,
+  pub index: u64,
+  pub proposer: Pubkey,
+  pub action: MultisigAction,
+  #[max_len(MAX_APPROVERS)]
+  pub approvals: Vec<Pubkey>,
+  pub bump: u8,
+}
+
+impl Proposal {
+  pub fn approve(&mut self, multisig: &Multisig, approver: &Pubkey) -> Result<()> {
+    multisig.ensure_approver(approver)?;
+    require!(
+      !self.approvals.contains(approver),
+      EscrowError::AlreadyApproved
+    );
+    self.approvals.push(*approver);
+    Ok(())
+  }
+
+  pub fn is_approved(&self, multisig: &Multisig) -> bool {
+    self.approvals.len() >= usize::from(multisig.threshold)
+  }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EscrowCounts {
+  pub created: u64,
+  pub filled: u64,
+  pub refunded: u64,
+}
+
+impl EscrowCounts {
+  fn increment(count: &mut u64) -> Result<()> {
+    *count = count.checked_add(1).ok_or(EscrowError::MathOverflow)?;
+    Ok(())
+  }
+
+  pub fn record_created(&mut self) -> Result<()> {
+    Self::increment(&mut self.created)
+  }
+
+  pub fn record_filled(&mut self) -> Result<()> {
+    Self::increment(&mut self.filled)
+  }
+
+  pub fn record_refunded(&mut self) -> Result<()> {
+    Self::increment(&mut self.refunded)
+  }
+}
+
+// Lifetime totals for one maker, `[b"maker_stats", maker]`, created alongside
+// the registry. Kept by make and every fill and refund path.
+#[derive(InitSpace)]
+#[account(discriminator = 9)]
+pub struct MakerStats {
+  pub maker: Pubkey,
+  pub counts: EscrowCounts,
+  pub bump: u8,
+}
+
+// Lifetime totals for one market, `[b"pair_stats", mint_a, mint_b]`, kept by
+// the same instructions as MakerStats
+#[derive(InitSpace)]
+#[account(discriminator = 10)]
+pub struct PairStats {
+  pub mint_a: Pubkey,
+  pub mint_b: Pubkey,
+  pub counts: EscrowCounts,
+  // Cumulative mint_a sold and mint_b paid for it
+  pub volume_a: u128,
+  pub volume_b: u128,
+  // The most recent fill; `last_fill_b / last_fill_a` is the last price
+  pub last_fill_a: u64,
+  pub last_fill_b: u64,
+  pub bump: u8,
+}
+
+impl PairStats {
+  pub fn record_fill(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+    self.counts.record_filled()?;
+    self.record_volume(amount_a, amount_b)
+  }
+
+  // A tranche adds to the volume without filling the escrow
+  pub fn record_volume(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+    self.volume_a = self
+      .volume_a
+      .checked_add(amount_a.into())
+      .ok_or(EscrowError::MathOverflow)?;
+    self.volume_b = self
+      .volume_b
+      .checked_add(amount_b.into())
+      .ok_or(EscrowError::MathOverflow)?;
+    self.last_fill_a = amount_a;
+    self.last_fill_b = amount_b;
+    Ok(())
+  }
+}
+
+// Program-wide settings, `[b"config"]`. The admin can pause every make, fill
+// and deposit in an incident, leaving refunds and withdrawals open, and hands
+// the role over in two steps: the current admin proposes a successor, who then
+// accepts.
+#[derive(InitSpace)]
+#[account(discriminator = 12)]
+pub struct Config {
+  pub admin: Pubkey,
+  pub pending_admin: Option<Pubkey>,
+  pub paused: bool,
+  // Make only accepts mints with an `Allowed` rule, rather than any mint
+  // without a `Denied` one
+  pub allowlist_only: bool,
+  // Fee a take pays its referrer, if it names one, on top of the maker's
+  // mint_b payment
+  pub referral_bps: u16,
+  // Owner every take price feed must have. Feeds are plain data accounts, so
+  // without this a maker could pin one they wrote themselves
+  pub oracle_program: Pubkey,
+  pub bump: u8,
+}
+
+impl Config {
+  pub const MAX_BPS: u16 = 10_000;
+  // The taker pays the fee, so makers always get their full price; this only
+  // caps what a front end can add to it
+  pub const MAX_REFERRAL_BPS: u16 = 1_000;
+
+  // The referrer's fee on top of `paid`, rounded down
+  pub fn referral_fee(&self, paid: u64) -> Result<u64> {
+    let fee = u128::from(paid) * u128::from(self.referral_bps) / u128::from(Self::MAX_BPS);
+    u64::try_from(fee).map_err(|_| error!(EscrowError::MathOverflow))
+  }
+
+  pub fn check_mint(&self, listing: Option<MintListing>) -> Result<()> {
+    match listing {
+      Some(MintListing::Denied) => err!(EscrowError::MintDenied),
+      None if self.allowlist_only => err!(EscrowError::MintNotAllowed),
+      _ => Ok(()),
+    }
+  }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MintListing {
+  Allowed,
+  Denied,
+}
+
+// The admin's listing of one mint, `[b"mint_rule", mint]`. One account per
+// mint keeps the allow and deny lists unbounded.
+#[derive(InitSpace)]
+#[account(discriminator = 13)]
+pub struct MintRule {
+  pub mint: Pubkey,
+  pub listing: MintListing,
+  pub bump: u8,
+}
+
+impl MintRule {
+  // The listing stored at a mint's rule address, or None while it is unlisted
+  pub fn read(info: &AccountInfo) -> Result<Option<MintListing>> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+      return Ok(None);
+    }
+    let rule = MintRule::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    Ok(Some(rule.listing))
+  }
 }
\ No newline at end of file
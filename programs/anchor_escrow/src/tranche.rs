@@ -0,0 +1,81 @@
+use crate::errors::EscrowError;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrancheSchedule {
+    // mint_a released per tranche; the last tranche takes whatever is left
+    pub size: u64,
+    // Seconds that must pass between two fills
+    pub interval: i64,
+    // Fixed mint_b price per full tranche, otherwise a pro-rata share of `receive`
+    pub receive: Option<u64>,
+}
+
+impl TrancheSchedule {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.size > 0 && self.interval > 0 && self.receive != Some(0),
+            EscrowError::InvalidTrancheSchedule
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tranches {
+    pub schedule: TrancheSchedule,
+    // Number of tranches filled so far
+    pub index: u32,
+    // Unix time of the latest fill
+    pub last_fill: i64,
+}
+
+impl Tranches {
+    pub fn new(schedule: TrancheSchedule) -> Self {
+        Self {
+            schedule,
+            index: 0,
+            last_fill: 0,
+        }
+    }
+
+    // The first tranche is available straight away, every later one an
+    // interval after the previous fill
+    pub fn ensure_ready(&self, now: i64) -> Result<()> {
+        if self.index > 0 {
+            require_gte!(
+                now,
+                self.last_fill.saturating_add(self.schedule.interval),
+                EscrowError::TrancheNotReady
+            );
+        }
+        Ok(())
+    }
+
+    pub fn is_last(&self, remaining: u64) -> bool {
+        remaining <= self.schedule.size
+    }
+
+    // Returns the mint_a released and the mint_b owed for the next tranche, out of
+    // `remaining` in the vault that the maker still wants `receive` for
+    pub fn next_fill(&self, remaining: u64, receive: u64) -> Result<(u64, u64)> {
+        let amount = remaining.min(self.schedule.size);
+        // Price of `per` mint_a, scaled to the tranche and rounded in the maker's favour
+        let (price, per) = match self.schedule.receive {
+            Some(price) => (price, self.schedule.size),
+            None => (receive, remaining),
+        };
+        let price = (price as u128)
+            .checked_mul(amount as u128)
+            .ok_or(EscrowError::MathOverflow)?
+            .div_ceil(per as u128);
+        let price = u64::try_from(price).map_err(|_| EscrowError::MathOverflow)?;
+        Ok((amount, price))
+    }
+
+    pub fn record_fill(&mut self, now: i64) -> Result<()> {
+        self.index = self.index.checked_add(1).ok_or(EscrowError::MathOverflow)?;
+        self.last_fill = now;
+        Ok(())
+    }
+}
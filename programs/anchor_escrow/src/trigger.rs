@@ -0,0 +1,35 @@
+use crate::{errors::EscrowError, oracle::Price};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderKind {
+    // Sell mint_a once the oracle price rises to the trigger
+    Limit,
+    // Sell mint_a once the oracle price falls to the trigger
+    Stop,
+}
+
+fn rescale(value: i64, exponent_gap: i32) -> Result<i128> {
+    10i128
+        .checked_pow(exponent_gap as u32)
+        .and_then(|factor| (value as i128).checked_mul(factor))
+        .ok_or(error!(EscrowError::MathOverflow))
+}
+
+// Compares the feed price against `trigger_price * 10^trigger_expo`, bringing
+// both to the finer of the two exponents first.
+pub fn crossed(
+    kind: OrderKind,
+    trigger_price: i64,
+    trigger_expo: i32,
+    price: &Price,
+) -> Result<bool> {
+    let expo = trigger_expo.min(price.expo);
+    let current = rescale(price.price, price.expo - expo)?;
+    let trigger = rescale(trigger_price, trigger_expo - expo)?;
+
+    Ok(match kind {
+        OrderKind::Limit => current >= trigger,
+        OrderKind::Stop => current <= trigger,
+    })
+}
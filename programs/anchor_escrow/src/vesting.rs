@@ -0,0 +1,37 @@
+use crate::errors::EscrowError;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VestingSchedule {
+    // Seconds after the take before anything unlocks
+    pub cliff: i64,
+    // Seconds after the take until everything is unlocked
+    pub duration: i64,
+}
+
+impl VestingSchedule {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.duration > 0 && (0..=self.duration).contains(&self.cliff),
+            EscrowError::InvalidVestingSchedule
+        );
+        Ok(())
+    }
+
+    // Linear unlock from `start`, with nothing released before the cliff. Rounds
+    // down so the last token only unlocks once the full duration has passed.
+    pub fn unlocked(&self, total: u64, start: i64, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(start);
+        if elapsed < self.cliff {
+            return Ok(0);
+        }
+        if elapsed >= self.duration {
+            return Ok(total);
+        }
+        let unlocked = (total as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(EscrowError::MathOverflow)?
+            / self.duration as u128;
+        Ok(unlocked as u64)
+    }
+}
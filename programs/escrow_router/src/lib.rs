@@ -0,0 +1,314 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_escrow::program::AnchorEscrow;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+declare_id!("CXv8wWo981gRiZQofhWagsWMaB2uP1vY7vSjA8WGy5MT");
+
+// Sample integration that trades through `anchor_escrow` by CPI. Each user gets
+// a "desk" PDA that holds their tokens and signs as maker or taker, while the
+// user pays all rent, so the desk itself never needs lamports.
+#[program]
+pub mod escrow_router {
+    use super::*;
+    pub fn route_make(ctx: Context<RouteMake>, seed: u64, receive: u64, amount: u64) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[b"desk", owner.as_ref(), &[ctx.bumps.desk]]];
+
+        anchor_escrow::cpi::make(
+            CpiContext::new_with_signer(
+                ctx.accounts.escrow_program.to_account_info(),
+                anchor_escrow::cpi::accounts::Make {
+                    payer: ctx.accounts.owner.to_account_info(),
+                    maker: ctx.accounts.desk.to_account_info(),
+                    config: ctx.accounts.config.to_account_info(),
+                    escrow: ctx.accounts.escrow.to_account_info(),
+                    registry: ctx.accounts.registry.to_account_info(),
+                    maker_stats: ctx.accounts.maker_stats.to_account_info(),
+                    pair_stats: ctx.accounts.pair_stats.to_account_info(),
+                    mint_a: ctx.accounts.mint_a.to_account_info(),
+                    mint_b: ctx.accounts.mint_b.to_account_info(),
+                    mint_a_rule: ctx.accounts.mint_a_rule.to_account_info(),
+                    mint_b_rule: ctx.accounts.mint_b_rule.to_account_info(),
+                    maker_ata_a: ctx.accounts.desk_ata_a.to_account_info(),
+                    vault: ctx.accounts.vault.to_account_info(),
+                    associated_token_program: ctx
+                        .accounts
+                        .associated_token_program
+                        .to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            seed,
+            receive,
+            amount,
+        )
+    }
+    pub fn route_take(ctx: Context<RouteTake>, max_receive: u64) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[b"desk", owner.as_ref(), &[ctx.bumps.desk]]];
+
+        anchor_escrow::cpi::take(
+            CpiContext::new_with_signer(
+                ctx.accounts.escrow_program.to_account_info(),
+                anchor_escrow::cpi::accounts::Take {
+                    payer: ctx.accounts.owner.to_account_info(),
+                    taker: ctx.accounts.desk.to_account_info(),
+                    maker: ctx.accounts.maker.to_account_info(),
+                    config: ctx.accounts.config.to_account_info(),
+                    escrow: ctx.accounts.escrow.to_account_info(),
+                    registry: ctx.accounts.registry.to_account_info(),
+                    maker_stats: ctx.accounts.maker_stats.to_account_info(),
+                    pair_stats: ctx.accounts.pair_stats.to_account_info(),
+                    mint_a: ctx.accounts.mint_a.to_account_info(),
+                    mint_b: ctx.accounts.mint_b.to_account_info(),
+                    vault: ctx.accounts.vault.to_account_info(),
+                    taker_ata_a: ctx.accounts.desk_ata_a.to_account_info(),
+                    taker_ata_b: ctx.accounts.desk_ata_b.to_account_info(),
+                    maker_ata_b: ctx.accounts.maker_ata_b.to_account_info(),
+                    associated_token_program: ctx
+                        .accounts
+                        .associated_token_program
+                        .to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    taker_gate_account: None,
+                    price_feed: None,
+                    vesting: None,
+                    vesting_vault: None,
+                    reservation: None,
+                    instructions_sysvar: ctx
+                        .accounts
+                        .instructions_sysvar
+                        .as_ref()
+                        .map(|sysvar| sysvar.to_account_info()),
+                    referrer: None,
+                    referrer_ata_b: None,
+                },
+                &signer_seeds,
+            ),
+            vec![],
+            anchor_escrow::TakeCleanup::default(),
+            max_receive,
+        )
+    }
+    pub fn route_refund(ctx: Context<RouteRefund>) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[b"desk", owner.as_ref(), &[ctx.bumps.desk]]];
+
+        anchor_escrow::cpi::refund(CpiContext::new_with_signer(
+            ctx.accounts.escrow_program.to_account_info(),
+            anchor_escrow::cpi::accounts::Refund {
+                maker: ctx.accounts.desk.to_account_info(),
+                escrow: ctx.accounts.escrow.to_account_info(),
+                registry: ctx.accounts.registry.to_account_info(),
+                maker_stats: ctx.accounts.maker_stats.to_account_info(),
+                pair_stats: ctx.accounts.pair_stats.to_account_info(),
+                mint_a: ctx.accounts.mint_a.to_account_info(),
+                vault: ctx.accounts.vault.to_account_info(),
+                maker_ata_a: ctx.accounts.desk_ata_a.to_account_info(),
+                associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            &signer_seeds,
+        ))?;
+
+        // Refund closes the escrow and vault to the desk, but the owner paid
+        // that rent, so it goes straight back to them
+        let lamports = ctx.accounts.desk.lamports();
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.desk.to_account_info(),
+                    to: ctx.accounts.owner.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            lamports,
+        )
+    }
+    // Moves `amount` of any mint the desk holds, such as take proceeds or a
+    // refund, out to the owner
+    pub fn withdraw_from_desk(ctx: Context<WithdrawFromDesk>, amount: u64) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[b"desk", owner.as_ref(), &[ctx.bumps.desk]]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.desk_ata.to_account_info(),
+                    to: ctx.accounts.owner_ata.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    authority: ctx.accounts.desk.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )
+    }
+}
+
+// Escrow-side accounts are left unchecked here; anchor_escrow validates them.
+#[derive(Accounts)]
+pub struct RouteMake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: signs as the escrow maker, never holds data
+    #[account(seeds = [b"desk", owner.key().as_ref()], bump)]
+    pub desk: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    pub config: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub registry: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub maker_stats: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub pair_stats: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    pub mint_a: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    pub mint_b: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    pub mint_a_rule: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    pub mint_b_rule: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub desk_ata_a: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+    pub escrow_program: Program<'info, AnchorEscrow>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RouteTake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: signs as the escrow taker, never holds data
+    #[account(mut, seeds = [b"desk", owner.key().as_ref()], bump)]
+    pub desk: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub maker: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    pub config: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub registry: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub maker_stats: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub pair_stats: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    pub mint_a: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    pub mint_b: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub desk_ata_a: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub desk_ata_b: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub maker_ata_b: UncheckedAccount<'info>,
+    pub escrow_program: Program<'info, AnchorEscrow>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: passed through for anchor_escrow, which rejects solo takes made
+    /// through CPI
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct RouteRefund<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: signs as the escrow maker, never holds data
+    #[account(mut, seeds = [b"desk", owner.key().as_ref()], bump)]
+    pub desk: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub registry: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub maker_stats: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub pair_stats: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    pub mint_a: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: validated by anchor_escrow
+    #[account(mut)]
+    pub desk_ata_a: UncheckedAccount<'info>,
+    pub escrow_program: Program<'info, AnchorEscrow>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Only the owner can derive the desk's signer, so no one else can empty it
+#[derive(Accounts)]
+pub struct WithdrawFromDesk<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: signs for its token accounts, never holds data
+    #[account(seeds = [b"desk", owner.key().as_ref()], bump)]
+    pub desk: UncheckedAccount<'info>,
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = desk,
+        associated_token::token_program = token_program,
+    )]
+    pub desk_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_ata: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,44 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+declare_id!("BCn7NspgJGFzK49vpFXyQsFQhFXcuzpGVJ3xDg8eWyWx");
+
+// Sample callback for `anchor_escrow::flash_take`. By the time it runs the taker
+// already holds the escrowed mint_a; a real integration would swap it here. This
+// one just pays `amount` of mint_b to the maker out of the taker's own account,
+// so tests can play both an honest and a short-paying taker.
+#[program]
+pub mod flash_callback {
+    use super::*;
+    pub fn repay(ctx: Context<Repay>, amount: u64) -> Result<()> {
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.taker_ata_b.to_account_info(),
+                    to: ctx.accounts.maker_ata_b.to_account_info(),
+                    mint: ctx.accounts.mint_b.to_account_info(),
+                    authority: ctx.accounts.taker.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.mint_b.decimals,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct Repay<'info> {
+    pub taker: Signer<'info>,
+    #[account(mint::token_program = token_program)]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(mut, token::mint = mint_b, token::authority = taker)]
+    pub taker_ata_b: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint_b)]
+    pub maker_ata_b: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
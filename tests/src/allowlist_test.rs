@@ -0,0 +1,134 @@
+use crate::common::{assert_escrow_error, take_instruction, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use crate::merkle_tree::MerkleTree;
+use anchor_escrow::errors::EscrowError;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::types::TransactionResult;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+fn set_allowlist_ix(f: &EscrowFixture, root: Option<[u8; 32]>) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetAllowlist {
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetAllowlist { root }.data(),
+    }
+}
+
+fn take_with_proof(f: &mut EscrowFixture, proof: Vec<[u8; 32]>) -> TransactionResult {
+    let accounts = f.take_accounts(SEED, &f.taker.pubkey(), &f.maker.pubkey());
+    let ix = take_instruction(accounts, proof);
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+// Makes an escrow restricted to the taker plus `others` random keys
+fn make_allowlisted(f: &mut EscrowFixture, others: usize) -> MerkleTree {
+    let mut keys: Vec<Pubkey> = (0..others).map(|_| Pubkey::new_unique()).collect();
+    keys.insert(others / 2, f.taker.pubkey());
+    let tree = MerkleTree::new(&keys);
+
+    let make_ix = f.make_ix(SEED, RECEIVE, AMOUNT);
+    let set_ix = set_allowlist_ix(f, Some(tree.root()));
+    let maker = f.maker.insecure_clone();
+    f.send(&[make_ix, set_ix], &[&maker]).unwrap();
+
+    tree
+}
+
+#[test]
+fn test_allowlisted_taker_can_take() {
+    let mut f = EscrowFixture::new();
+    let tree = make_allowlisted(&mut f, 6);
+    assert_eq!(f.escrow_state(SEED).allowlist_root(), Some(tree.root()));
+
+    let proof = tree.proof(&f.taker.pubkey()).unwrap();
+    take_with_proof(&mut f, proof).unwrap();
+
+    let taker_ata_a = get_associated_token_address(&f.taker.pubkey(), &f.mint_a.pubkey());
+    assert_eq!(f.token_balance(&taker_ata_a), AMOUNT);
+}
+
+#[test]
+fn test_single_member_allowlist_needs_no_proof() {
+    let mut f = EscrowFixture::new();
+    let tree = make_allowlisted(&mut f, 0);
+
+    assert!(tree.proof(&f.taker.pubkey()).unwrap().is_empty());
+    take_with_proof(&mut f, vec![]).unwrap();
+}
+
+#[test]
+fn test_non_member_is_rejected() {
+    let mut f = EscrowFixture::new();
+    let tree = make_allowlisted(&mut f, 6);
+
+    assert!(tree.proof(&Keypair::new().pubkey()).is_none());
+
+    // A proof that is valid in another tree doesn't help the taker
+    let other_member = Pubkey::new_unique();
+    let proof = MerkleTree::new(&[other_member, Pubkey::new_unique()])
+        .proof(&other_member)
+        .unwrap();
+    assert_escrow_error(take_with_proof(&mut f, proof), EscrowError::TakerNotAllowed);
+    assert_escrow_error(
+        take_with_proof(&mut f, vec![]),
+        EscrowError::TakerNotAllowed,
+    );
+}
+
+#[test]
+fn test_malformed_proofs_are_rejected() {
+    let mut f = EscrowFixture::new();
+    let tree = make_allowlisted(&mut f, 6);
+    let proof = tree.proof(&f.taker.pubkey()).unwrap();
+
+    let mut flipped = proof.clone();
+    flipped[0][0] ^= 1;
+    assert_escrow_error(
+        take_with_proof(&mut f, flipped),
+        EscrowError::TakerNotAllowed,
+    );
+
+    let mut truncated = proof.clone();
+    truncated.pop();
+    assert_escrow_error(
+        take_with_proof(&mut f, truncated),
+        EscrowError::TakerNotAllowed,
+    );
+
+    let mut extended = proof.clone();
+    extended.push([7u8; 32]);
+    assert_escrow_error(
+        take_with_proof(&mut f, extended),
+        EscrowError::TakerNotAllowed,
+    );
+
+    let oversized = vec![[0u8; 32]; anchor_escrow::merkle::MAX_PROOF_LEN + 1];
+    assert_escrow_error(
+        take_with_proof(&mut f, oversized),
+        EscrowError::ProofTooLong,
+    );
+
+    // The escrow is untouched and the real proof still works
+    take_with_proof(&mut f, proof).unwrap();
+}
+
+#[test]
+fn test_maker_can_lift_the_allowlist() {
+    let mut f = EscrowFixture::new();
+    make_allowlisted(&mut f, 6);
+
+    let ix = set_allowlist_ix(&f, None);
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+
+    take_with_proof(&mut f, vec![]).unwrap();
+}
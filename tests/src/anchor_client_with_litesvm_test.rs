@@ -1,3 +1,4 @@
+use crate::common::{deploy_upgradeable, program_data_address};
 use litesvm::LiteSVM;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
@@ -33,6 +34,9 @@ fn test_make_and_take_with_anchor_client() {
     svm.airdrop(&maker.pubkey(), 10_000_000_000).unwrap();
     svm.airdrop(&taker.pubkey(), 10_000_000_000).unwrap();
 
+    // init_config only accepts the program's upgrade authority as admin
+    deploy_upgradeable(&mut svm, &maker.pubkey());
+
     // Create two token mints
     let mint_a = Keypair::new();
     let mint_b = Keypair::new();
@@ -158,14 +162,69 @@ fn test_make_and_take_with_anchor_client() {
 
     let program: Program<Rc<Keypair>> = client.program(program_id).unwrap();
 
+    let (registry, _bump) = Pubkey::find_program_address(
+        &[b"registry", maker.pubkey().as_ref()],
+        &program_id,
+    );
+    let (maker_stats, _bump) = Pubkey::find_program_address(
+        &[b"maker_stats", maker.pubkey().as_ref()],
+        &program_id,
+    );
+    let (pair_stats, _bump) = Pubkey::find_program_address(
+        &[b"pair_stats", mint_a.pubkey().as_ref(), mint_b.pubkey().as_ref()],
+        &program_id,
+    );
+
+    // The program config is created once per deployment, by its admin
+    let (config, _bump) = Pubkey::find_program_address(&[b"config"], &program_id);
+    let (mint_a_rule, _bump) =
+        Pubkey::find_program_address(&[b"mint_rule", mint_a.pubkey().as_ref()], &program_id);
+    let (mint_b_rule, _bump) =
+        Pubkey::find_program_address(&[b"mint_rule", mint_b.pubkey().as_ref()], &program_id);
+    let init_config_ix = program
+        .request()
+        .accounts(anchor_escrow::client::accounts::InitConfig {
+            admin: maker.pubkey(),
+            program: program_id,
+            program_data: program_data_address(),
+            config,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(anchor_escrow::client::args::InitConfig {})
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    // Make opens the registry on first use, but a maker can open it up front
+    let init_registry_ix = program
+        .request()
+        .accounts(anchor_escrow::client::accounts::InitRegistry {
+            payer: maker.pubkey(),
+            maker: maker.pubkey(),
+            registry,
+            maker_stats,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(anchor_escrow::client::args::InitRegistry {})
+        .instructions()
+        .unwrap()
+        .remove(0);
+
     // Create the escrow first
     let make_ix = program
         .request()
         .accounts(anchor_escrow::client::accounts::Make {
+            payer: maker.pubkey(),
             maker: maker.pubkey(),
+            config,
             escrow: escrow_pda,
+            registry,
+            maker_stats,
+            pair_stats,
             mint_a: mint_a.pubkey(),
             mint_b: mint_b.pubkey(),
+            mint_a_rule,
+            mint_b_rule,
             maker_ata_a,
             vault,
             associated_token_program: spl_associated_token_account::id(),
@@ -183,7 +242,7 @@ fn test_make_and_take_with_anchor_client() {
 
     // Send make transaction
     let tx = Transaction::new_signed_with_payer(
-        &[make_ix],
+        &[init_config_ix, init_registry_ix, make_ix],
         Some(&maker.pubkey()),
         &[&maker],
         svm.latest_blockhash(),
@@ -199,9 +258,14 @@ fn test_make_and_take_with_anchor_client() {
     let take_ix = program
         .request()
         .accounts(anchor_escrow::client::accounts::Take {
+            payer: taker.pubkey(),
             taker: taker.pubkey(),
             maker: maker.pubkey(),
+            config,
             escrow: escrow_pda,
+            registry,
+            maker_stats,
+            pair_stats,
             mint_a: mint_a.pubkey(),
             mint_b: mint_b.pubkey(),
             vault,
@@ -211,8 +275,20 @@ fn test_make_and_take_with_anchor_client() {
             associated_token_program: spl_associated_token_account::id(),
             token_program: spl_token::id(),
             system_program: solana_sdk::system_program::id(),
+            taker_gate_account: None,
+            price_feed: None,
+            vesting: None,
+            vesting_vault: None,
+            reservation: None,
+            instructions_sysvar: None,
+            referrer: None,
+            referrer_ata_b: None,
+        })
+        .args(anchor_escrow::client::args::Take {
+            proof: vec![],
+            cleanup: anchor_escrow::TakeCleanup::default(),
+            max_receive: 500_000_000,
         })
-        .args(anchor_escrow::client::args::Take {})
         .instructions()
         .unwrap()
         .remove(0);
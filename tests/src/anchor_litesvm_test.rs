@@ -1,3 +1,4 @@
+use crate::common::{deploy_upgradeable, program_data_address};
 use anchor_litesvm::{
     AnchorLiteSVM, AssertionHelpers, TestHelpers, tuple_args,
 };
@@ -17,6 +18,8 @@ fn test_make_and_take_with_anchor_litesvm() {
     // Create all test accounts in just 4 lines
     let maker = ctx.create_funded_account(10_000_000_000).unwrap();
     let taker = ctx.create_funded_account(10_000_000_000).unwrap();
+    // init_config only accepts the program's upgrade authority as admin
+    deploy_upgradeable(&mut ctx.svm, &maker.pubkey());
     let mint_a = ctx.create_token_mint(&maker, 9).unwrap();
     let mint_b = ctx.create_token_mint(&maker, 9).unwrap();
 
@@ -28,13 +31,51 @@ fn test_make_and_take_with_anchor_litesvm() {
     let seed = 42u64;
     let (escrow_pda, _) = ctx.find_pda(&[b"escrow", maker.pubkey().as_ref(), &seed.to_le_bytes()]);
     let vault = get_associated_token_address(&escrow_pda, &mint_a.pubkey());
+    let (registry, _) = ctx.find_pda(&[b"registry", maker.pubkey().as_ref()]);
+    let (maker_stats, _) = ctx.find_pda(&[b"maker_stats", maker.pubkey().as_ref()]);
+    let (pair_stats, _) =
+        ctx.find_pda(&[b"pair_stats", mint_a.pubkey().as_ref(), mint_b.pubkey().as_ref()]);
+
+    // The program config is created once per deployment, by its admin
+    let (config, _) = ctx.find_pda(&[b"config"]);
+    let (mint_a_rule, _) = ctx.find_pda(&[b"mint_rule", mint_a.pubkey().as_ref()]);
+    let (mint_b_rule, _) = ctx.find_pda(&[b"mint_rule", mint_b.pubkey().as_ref()]);
+    ctx.instruction_builder("init_config")
+        .signer("admin", &maker)
+        .account("program", anchor_escrow::ID)
+        .account("program_data", program_data_address())
+        .account_mut("config", config)
+        .system_program()
+        .args(tuple_args(()))
+        .execute(&mut ctx, &[&maker])
+        .unwrap()
+        .assert_success();
+
+    // Make opens the registry on first use, but a maker can open it up front
+    ctx.instruction_builder("init_registry")
+        .signer("payer", &maker)
+        .account("maker", maker.pubkey())
+        .account_mut("registry", registry)
+        .account_mut("maker_stats", maker_stats)
+        .system_program()
+        .args(tuple_args(()))
+        .execute(&mut ctx, &[&maker])
+        .unwrap()
+        .assert_success();
 
     // MAKE: Build and execute in one expression
     ctx.instruction_builder("make")
+        .signer("payer", &maker)
         .signer("maker", &maker)
+        .account("config", config)
         .account_mut("escrow", escrow_pda)
+        .account_mut("registry", registry)
+        .account_mut("maker_stats", maker_stats)
+        .account_mut("pair_stats", pair_stats)
         .account("mint_a", mint_a.pubkey())
         .account("mint_b", mint_b.pubkey())
+        .account("mint_a_rule", mint_a_rule) // unlisted mints leave these empty
+        .account("mint_b_rule", mint_b_rule)
         .account_mut("maker_ata_a", maker_ata_a)
         .account_mut("vault", vault)
         .associated_token_program()
@@ -55,9 +96,14 @@ fn test_make_and_take_with_anchor_litesvm() {
     let maker_ata_b = get_associated_token_address(&maker.pubkey(), &mint_b.pubkey());
 
     ctx.instruction_builder("take")
+        .signer("payer", &taker)
         .signer("taker", &taker)
         .account_mut("maker", maker.pubkey())
+        .account("config", config)
         .account_mut("escrow", escrow_pda)
+        .account_mut("registry", registry)
+        .account_mut("maker_stats", maker_stats)
+        .account_mut("pair_stats", pair_stats)
         .account("mint_a", mint_a.pubkey())
         .account("mint_b", mint_b.pubkey())
         .account_mut("vault", vault)
@@ -67,7 +113,16 @@ fn test_make_and_take_with_anchor_litesvm() {
         .associated_token_program()
         .token_program()
         .system_program()
-        .args(tuple_args(()))
+        .account("taker_gate_account", ctx.program_id) // optional, not token-gated
+        .account("price_feed", ctx.program_id) // optional, fixed price
+        .account("vesting", ctx.program_id) // optional, pays out immediately
+        .account("vesting_vault", ctx.program_id)
+        .account("reservation", ctx.program_id) // optional, not reserved
+        .account("instructions_sysvar", ctx.program_id) // optional, not a solo take
+        .account("referrer", ctx.program_id) // optional, no referral
+        .account("referrer_ata_b", ctx.program_id)
+        // No allowlist proof or cleanup, paying at most the asking price
+        .args(tuple_args((Vec::<[u8; 32]>::new(), anchor_escrow::TakeCleanup::default(), 500_000_000u64)))
         .execute(&mut ctx, &[&taker])
         .unwrap()
         .assert_success();
@@ -0,0 +1,517 @@
+#![allow(dead_code)]
+
+use anchor_escrow::state::{Escrow, EscrowCounts, MakerStats, PairStats};
+use anchor_escrow::TakeCleanup;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_litesvm::{AnchorContext, AnchorLiteSVM, TestHelpers};
+use litesvm::{types::TransactionResult, LiteSVM};
+use solana_program_pack::Pack;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+// Shared setup for the feature tests: a funded maker and taker, two 9-decimal
+// mints and the maker/taker token accounts the basic make -> take flow needs.
+
+pub const SEED: u64 = 42;
+pub const RECEIVE: u64 = 500_000_000; // 0.5 tokens
+pub const AMOUNT: u64 = 1_000_000_000; // 1 token
+
+pub struct EscrowFixture {
+    pub ctx: AnchorContext,
+    pub admin: Keypair,
+    pub maker: Keypair,
+    pub taker: Keypair,
+    pub mint_a: Keypair,
+    pub mint_b: Keypair,
+    pub maker_ata_a: Pubkey,
+    pub taker_ata_b: Pubkey,
+}
+
+impl EscrowFixture {
+    pub fn new() -> Self {
+        let mut fixture = Self::without_registry();
+        let maker = fixture.maker.pubkey();
+        fixture.init_registry(&maker).unwrap();
+        fixture
+    }
+
+    // The maker's registry and stats are left for their first make to create
+    pub fn without_registry() -> Self {
+        let mut ctx = AnchorLiteSVM::build_with_program(anchor_escrow::ID, PROGRAM_BYTES);
+
+        let admin = ctx.create_funded_account(1_000_000_000).unwrap();
+        deploy_upgradeable(&mut ctx.svm, &admin.pubkey());
+        let maker = ctx.create_funded_account(10_000_000_000).unwrap();
+        let taker = ctx.create_funded_account(10_000_000_000).unwrap();
+        let mint_a = ctx.create_token_mint(&maker, 9).unwrap();
+        let mint_b = ctx.create_token_mint(&maker, 9).unwrap();
+
+        let maker_ata_a = ctx
+            .create_token_account(&maker, &mint_a.pubkey(), Some((AMOUNT, &maker)))
+            .unwrap();
+        let taker_ata_b = ctx
+            .create_token_account(&taker, &mint_b.pubkey(), Some((RECEIVE, &maker)))
+            .unwrap();
+
+        let mut fixture = Self {
+            ctx,
+            admin,
+            maker,
+            taker,
+            mint_a,
+            mint_b,
+            maker_ata_a,
+            taker_ata_b,
+        };
+        fixture.init_config().unwrap();
+        fixture
+    }
+
+    pub fn escrow(&self, seed: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"escrow", self.maker.pubkey().as_ref(), &seed.to_le_bytes()],
+            &anchor_escrow::ID,
+        )
+        .0
+    }
+
+    pub fn config(&self) -> Pubkey {
+        config_address()
+    }
+
+    pub fn registry(&self, maker: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"registry", maker.as_ref()], &anchor_escrow::ID).0
+    }
+
+    pub fn maker_stats(&self, maker: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"maker_stats", maker.as_ref()], &anchor_escrow::ID).0
+    }
+
+    pub fn pair_stats(&self) -> Pubkey {
+        pair_stats_address(&self.mint_a.pubkey(), &self.mint_b.pubkey())
+    }
+
+    pub fn vault(&self, seed: u64) -> Pubkey {
+        get_associated_token_address(&self.escrow(seed), &self.mint_a.pubkey())
+    }
+
+    // The vault of an escrow made with `make_with_pda_vault`
+    pub fn pda_vault(&self, seed: u64) -> Pubkey {
+        Pubkey::find_program_address(&[b"vault", self.escrow(seed).as_ref()], &anchor_escrow::ID).0
+    }
+
+    pub fn escrow_state(&self, seed: u64) -> Escrow {
+        let account = self.ctx.svm.get_account(&self.escrow(seed)).unwrap();
+        Escrow::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn maker_stats_state(&self, maker: &Pubkey) -> MakerStats {
+        let account = self.ctx.svm.get_account(&self.maker_stats(maker)).unwrap();
+        MakerStats::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn pair_stats_state(&self) -> PairStats {
+        let account = self.ctx.svm.get_account(&self.pair_stats()).unwrap();
+        PairStats::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    // In LiteSVM, closed accounts might still exist with 0 lamports and 0 data
+    pub fn is_closed(&self, address: &Pubkey) -> bool {
+        match self.ctx.svm.get_account(address) {
+            None => true,
+            Some(account) => account.lamports == 0 && account.data.is_empty(),
+        }
+    }
+
+    pub fn token_balance(&self, address: &Pubkey) -> u64 {
+        let account = self.ctx.svm.get_account(address).unwrap();
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+
+    pub fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> TransactionResult {
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.ctx.svm.latest_blockhash(),
+        );
+        let result = self.ctx.svm.send_transaction(tx);
+        // Identical retries would otherwise be rejected as already processed.
+        self.ctx.svm.expire_blockhash();
+        result
+    }
+
+    pub fn init_config(&mut self) -> TransactionResult {
+        let ix = init_config_instruction(&self.admin.pubkey());
+        let admin = self.admin.insecure_clone();
+        self.send(&[ix], &[&admin])
+    }
+
+    pub fn pause(&mut self) {
+        let ix = Instruction {
+            program_id: anchor_escrow::ID,
+            accounts: anchor_escrow::accounts::SetPaused {
+                admin: self.admin.pubkey(),
+                config: self.config(),
+            }
+            .to_account_metas(None),
+            data: anchor_escrow::instruction::SetPaused { paused: true }.data(),
+        };
+        let admin = self.admin.insecure_clone();
+        self.send(&[ix], &[&admin]).unwrap();
+    }
+
+    pub fn init_registry(&mut self, maker: &Pubkey) -> TransactionResult {
+        let ix = Instruction {
+            program_id: anchor_escrow::ID,
+            accounts: anchor_escrow::accounts::InitRegistry {
+                payer: self.maker.pubkey(),
+                maker: *maker,
+                registry: self.registry(maker),
+                maker_stats: self.maker_stats(maker),
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: anchor_escrow::instruction::InitRegistry {}.data(),
+        };
+        let payer = self.maker.insecure_clone();
+        self.send(&[ix], &[&payer])
+    }
+
+    pub fn make_accounts(&self, seed: u64) -> anchor_escrow::accounts::Make {
+        anchor_escrow::accounts::Make {
+            payer: self.maker.pubkey(),
+            maker: self.maker.pubkey(),
+            config: self.config(),
+            escrow: self.escrow(seed),
+            registry: self.registry(&self.maker.pubkey()),
+            maker_stats: self.maker_stats(&self.maker.pubkey()),
+            pair_stats: self.pair_stats(),
+            mint_a: self.mint_a.pubkey(),
+            mint_b: self.mint_b.pubkey(),
+            mint_a_rule: mint_rule_address(&self.mint_a.pubkey()),
+            mint_b_rule: mint_rule_address(&self.mint_b.pubkey()),
+            maker_ata_a: self.maker_ata_a,
+            vault: self.vault(seed),
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+    }
+
+    pub fn make_ix(&self, seed: u64, receive: u64, amount: u64) -> Instruction {
+        make_instruction(self.make_accounts(seed), seed, receive, amount)
+    }
+
+    pub fn make_with_pda_vault_ix(&self, seed: u64, receive: u64, amount: u64) -> Instruction {
+        let mut ix = self.make_ix(seed, receive, amount);
+        with_vault(&mut ix, &self.vault(seed), &self.pda_vault(seed));
+        ix.data = anchor_escrow::instruction::MakeWithPdaVault {
+            seed,
+            receive,
+            amount,
+        }
+        .data();
+        ix
+    }
+
+    pub fn make_with_pda_vault(&mut self, seed: u64) -> TransactionResult {
+        let ix = self.make_with_pda_vault_ix(seed, RECEIVE, AMOUNT);
+        let maker = self.maker.insecure_clone();
+        self.send(&[ix], &[&maker])
+    }
+
+    pub fn make(&mut self, seed: u64) -> TransactionResult {
+        let ix = self.make_ix(seed, RECEIVE, AMOUNT);
+        let maker = self.maker.insecure_clone();
+        self.send(&[ix], &[&maker])
+    }
+
+    pub fn take_accounts(
+        &self,
+        seed: u64,
+        taker: &Pubkey,
+        maker: &Pubkey,
+    ) -> anchor_escrow::accounts::Take {
+        anchor_escrow::accounts::Take {
+            payer: *taker,
+            taker: *taker,
+            maker: *maker,
+            config: self.config(),
+            escrow: self.escrow(seed),
+            registry: self.registry(maker),
+            maker_stats: self.maker_stats(maker),
+            pair_stats: self.pair_stats(),
+            mint_a: self.mint_a.pubkey(),
+            mint_b: self.mint_b.pubkey(),
+            vault: self.vault(seed),
+            taker_ata_a: get_associated_token_address(taker, &self.mint_a.pubkey()),
+            taker_ata_b: get_associated_token_address(taker, &self.mint_b.pubkey()),
+            maker_ata_b: get_associated_token_address(maker, &self.mint_b.pubkey()),
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+            taker_gate_account: None,
+            price_feed: None,
+            vesting: None,
+            vesting_vault: None,
+            reservation: None,
+            instructions_sysvar: None,
+            referrer: None,
+            referrer_ata_b: None,
+        }
+    }
+
+    pub fn take_ix(&self, seed: u64, taker: &Pubkey, maker: &Pubkey) -> Instruction {
+        take_instruction(self.take_accounts(seed, taker, maker), vec![])
+    }
+
+    pub fn take(&mut self, seed: u64) -> TransactionResult {
+        let ix = self.take_ix(seed, &self.taker.pubkey(), &self.maker.pubkey());
+        let taker = self.taker.insecure_clone();
+        self.send(&[ix], &[&taker])
+    }
+
+    pub fn take_from_pda_vault(&mut self, seed: u64) -> TransactionResult {
+        let mut accounts = self.take_accounts(seed, &self.taker.pubkey(), &self.maker.pubkey());
+        accounts.vault = self.pda_vault(seed);
+        let taker = self.taker.insecure_clone();
+        self.send(&[take_instruction(accounts, vec![])], &[&taker])
+    }
+
+    pub fn refund_ix(&self, seed: u64, maker: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: anchor_escrow::ID,
+            accounts: anchor_escrow::accounts::Refund {
+                maker: *maker,
+                escrow: self.escrow(seed),
+                registry: self.registry(maker),
+                maker_stats: self.maker_stats(maker),
+                pair_stats: self.pair_stats(),
+                mint_a: self.mint_a.pubkey(),
+                vault: self.vault(seed),
+                maker_ata_a: get_associated_token_address(maker, &self.mint_a.pubkey()),
+                associated_token_program: spl_associated_token_account::id(),
+                token_program: spl_token::id(),
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: anchor_escrow::instruction::Refund {}.data(),
+        }
+    }
+
+    pub fn refund(&mut self, seed: u64) -> TransactionResult {
+        let ix = self.refund_ix(seed, &self.maker.pubkey());
+        let maker = self.maker.insecure_clone();
+        self.send(&[ix], &[&maker])
+    }
+
+    // Refunds the maker's escrows with `seeds`, listed in registry order
+    pub fn refund_all_ix(&self, seeds: &[u64]) -> Instruction {
+        let mut accounts = anchor_escrow::accounts::RefundAll {
+            maker: self.maker.pubkey(),
+            registry: self.registry(&self.maker.pubkey()),
+            maker_stats: self.maker_stats(&self.maker.pubkey()),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None);
+
+        for seed in seeds {
+            accounts.extend([
+                AccountMeta::new(self.escrow(*seed), false),
+                AccountMeta::new_readonly(self.mint_a.pubkey(), false),
+                AccountMeta::new(self.vault(*seed), false),
+                AccountMeta::new(self.maker_ata_a, false),
+                AccountMeta::new(self.pair_stats(), false),
+            ]);
+        }
+
+        Instruction {
+            program_id: anchor_escrow::ID,
+            accounts,
+            data: anchor_escrow::instruction::RefundAll {}.data(),
+        }
+    }
+
+    pub fn refund_from_pda_vault(&mut self, seed: u64) -> TransactionResult {
+        let mut ix = self.refund_ix(seed, &self.maker.pubkey());
+        with_vault(&mut ix, &self.vault(seed), &self.pda_vault(seed));
+        let maker = self.maker.insecure_clone();
+        self.send(&[ix], &[&maker])
+    }
+}
+
+pub const PROGRAM_BYTES: &[u8] = include_bytes!("../../target/deploy/anchor_escrow.so");
+
+// Spelled out because solana_sdk's loader module is deprecated
+const BPF_LOADER_UPGRADEABLE: Pubkey = pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+// Tag, slot and optional upgrade authority ahead of the ELF
+const PROGRAM_DATA_METADATA_LEN: usize = 4 + 8 + 1 + 32;
+
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[anchor_escrow::ID.as_ref()], &BPF_LOADER_UPGRADEABLE).0
+}
+
+// Redeploys anchor_escrow under the upgradeable loader with `authority` as its
+// upgrade authority, which init_config checks. `add_program` deploys it without
+// any program data.
+pub fn deploy_upgradeable(svm: &mut LiteSVM, authority: &Pubkey) {
+    let program_data = program_data_address();
+
+    // Bincode layouts of `UpgradeableLoaderState::ProgramData` and `::Program`
+    let mut data = Vec::with_capacity(PROGRAM_DATA_METADATA_LEN + PROGRAM_BYTES.len());
+    data.extend_from_slice(&3u32.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(authority.as_ref());
+    data.extend_from_slice(PROGRAM_BYTES);
+    set_loader_account(svm, program_data, data, false);
+
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(program_data.as_ref());
+    set_loader_account(svm, anchor_escrow::ID, data, true);
+}
+
+fn set_loader_account(svm: &mut LiteSVM, address: Pubkey, data: Vec<u8>, executable: bool) {
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: BPF_LOADER_UPGRADEABLE,
+        executable,
+        rent_epoch: 0,
+    };
+    svm.set_account(address, account).unwrap();
+}
+
+pub fn init_config_instruction(admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::InitConfig {
+            admin: *admin,
+            program: anchor_escrow::ID,
+            program_data: program_data_address(),
+            config: config_address(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::InitConfig {}.data(),
+    }
+}
+
+pub fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &anchor_escrow::ID).0
+}
+
+pub fn mint_rule_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"mint_rule", mint.as_ref()], &anchor_escrow::ID).0
+}
+
+pub fn counts(created: u64, filled: u64, refunded: u64) -> EscrowCounts {
+    EscrowCounts {
+        created,
+        filled,
+        refunded,
+    }
+}
+
+pub fn pair_stats_address(mint_a: &Pubkey, mint_b: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"pair_stats", mint_a.as_ref(), mint_b.as_ref()],
+        &anchor_escrow::ID,
+    )
+    .0
+}
+
+pub fn make_instruction(
+    accounts: anchor_escrow::accounts::Make,
+    seed: u64,
+    receive: u64,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: accounts.to_account_metas(None),
+        data: anchor_escrow::instruction::Make {
+            seed,
+            receive,
+            amount,
+        }
+        .data(),
+    }
+}
+
+// Points an instruction built for the ATA vault at another vault
+pub fn with_vault(ix: &mut Instruction, ata_vault: &Pubkey, vault: &Pubkey) {
+    for meta in ix
+        .accounts
+        .iter_mut()
+        .filter(|meta| meta.pubkey == *ata_vault)
+    {
+        meta.pubkey = *vault;
+    }
+}
+
+pub fn take_instruction(
+    accounts: anchor_escrow::accounts::Take,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    take_instruction_with_cleanup(accounts, proof, TakeCleanup::default())
+}
+
+// Leaves what the taker pays uncapped, for tests that aren't about `max_receive`
+pub fn take_instruction_with_cleanup(
+    accounts: anchor_escrow::accounts::Take,
+    proof: Vec<[u8; 32]>,
+    cleanup: TakeCleanup,
+) -> Instruction {
+    take_instruction_with_limit(accounts, proof, cleanup, u64::MAX)
+}
+
+pub fn take_instruction_with_limit(
+    accounts: anchor_escrow::accounts::Take,
+    proof: Vec<[u8; 32]>,
+    cleanup: TakeCleanup,
+    max_receive: u64,
+) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: accounts.to_account_metas(None),
+        data: anchor_escrow::instruction::Take {
+            proof,
+            cleanup,
+            max_receive,
+        }
+        .data(),
+    }
+}
+
+pub fn assert_escrow_error(result: TransactionResult, error: anchor_escrow::errors::EscrowError) {
+    let code: u32 = error.into();
+    match result {
+        Ok(_) => panic!("expected error {} but the transaction succeeded", code),
+        Err(failed) => match failed.err {
+            TransactionError::InstructionError(_, InstructionError::Custom(actual)) => {
+                assert_eq!(
+                    actual, code,
+                    "unexpected error code\nlogs: {:#?}",
+                    failed.meta.logs
+                )
+            }
+            other => panic!("expected error {} but got {:?}", code, other),
+        },
+    }
+}
@@ -0,0 +1,79 @@
+use crate::common::{EscrowFixture, SEED};
+use litesvm::types::TransactionResult;
+
+// Compute unit ceilings for the hot paths. These are provisional upper bounds,
+// not yet calibrated against a measured run; each budget is meant to sit about
+// 10% above what its instruction uses. A change that makes one of them
+// noticeably more expensive should then fail here and raise its budget on
+// purpose.
+const MAKE_BUDGET: u64 = 80_000;
+const TAKE_BUDGET: u64 = 80_000;
+const REFUND_BUDGET: u64 = 40_000;
+
+fn units(result: TransactionResult) -> u64 {
+    result.unwrap().compute_units_consumed
+}
+
+fn assert_within_budget(name: &str, result: TransactionResult, budget: u64) {
+    let units = units(result);
+    assert!(
+        units <= budget,
+        "{name} used {units} CU, over its {budget} budget"
+    );
+}
+
+#[test]
+fn test_make_stays_within_budget() {
+    let mut f = EscrowFixture::new();
+    assert_within_budget("make", f.make(SEED), MAKE_BUDGET);
+}
+
+#[test]
+fn test_take_stays_within_budget() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    assert_within_budget("take", f.take(SEED), TAKE_BUDGET);
+}
+
+#[test]
+fn test_refund_stays_within_budget() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    assert_within_budget("refund", f.refund(SEED), REFUND_BUDGET);
+}
+
+// The PDA vault skips the ATA program in make, while take and refund check
+// either kind of vault by its address alone
+#[test]
+fn test_make_with_pda_vault_is_cheaper() {
+    let mut ata = EscrowFixture::new();
+    let mut pda = EscrowFixture::new();
+    let ata_units = units(ata.make(SEED));
+    let pda_units = units(pda.make_with_pda_vault(SEED));
+    assert!(pda_units < ata_units);
+    assert!(pda_units <= MAKE_BUDGET);
+}
+
+#[test]
+fn test_take_costs_the_same_in_both_vault_modes() {
+    let mut ata = EscrowFixture::new();
+    let mut pda = EscrowFixture::new();
+    ata.make(SEED).unwrap();
+    pda.make_with_pda_vault(SEED).unwrap();
+    let ata_units = units(ata.take(SEED));
+    let pda_units = units(pda.take_from_pda_vault(SEED));
+    assert!(pda_units <= TAKE_BUDGET);
+    assert!(ata_units.abs_diff(pda_units) < 5_000);
+}
+
+#[test]
+fn test_refund_costs_the_same_in_both_vault_modes() {
+    let mut ata = EscrowFixture::new();
+    let mut pda = EscrowFixture::new();
+    ata.make(SEED).unwrap();
+    pda.make_with_pda_vault(SEED).unwrap();
+    let ata_units = units(ata.refund(SEED));
+    let pda_units = units(pda.refund_from_pda_vault(SEED));
+    assert!(pda_units <= REFUND_BUDGET);
+    assert!(ata_units.abs_diff(pda_units) < 5_000);
+}
@@ -0,0 +1,438 @@
+use crate::common::{
+    assert_escrow_error, counts, mint_rule_address, pair_stats_address, EscrowFixture, AMOUNT,
+    RECEIVE, SEED,
+};
+use crate::mock_oracle::{set_unix_timestamp, write_price, MockPrice};
+use anchor_escrow::{errors::EscrowError, state::PairStats, trigger::OrderKind};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_litesvm::TestHelpers;
+use solana_sdk::{clock::Clock, instruction::Instruction, pubkey::Pubkey, signature::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+const BOUNTY: u64 = 10_000_000;
+const MAX_STALENESS: u64 = 30;
+
+fn order(f: &EscrowFixture) -> Pubkey {
+    Pubkey::find_program_address(&[b"order", f.escrow(SEED).as_ref()], &anchor_escrow::ID).0
+}
+
+fn place_order_ix(
+    f: &EscrowFixture,
+    feed: Pubkey,
+    kind: OrderKind,
+    trigger_price: i64,
+) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::PlaceOrder {
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+            order: order(f),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::PlaceOrder {
+            feed,
+            kind,
+            trigger_price,
+            trigger_expo: -8,
+            max_staleness: MAX_STALENESS,
+            bounty: BOUNTY,
+        }
+        .data(),
+    }
+}
+
+fn trigger_order_ix(f: &EscrowFixture, keeper: &Pubkey, feed: Pubkey) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::TriggerOrder {
+            keeper: *keeper,
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+            order: order(f),
+            feed,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::TriggerOrder {}.data(),
+    }
+}
+
+// Passes the escrow either as open or as refunded, or not at all
+fn cancel_order_ix(
+    f: &EscrowFixture,
+    escrow: Option<Pubkey>,
+    refunded_escrow: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::CancelOrder {
+            maker: f.maker.pubkey(),
+            order: order(f),
+            escrow,
+            refunded_escrow,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::CancelOrder {}.data(),
+    }
+}
+
+// Makes the escrow and places a conditional order on it, with the feed quoting `price`
+fn make_conditional(
+    f: &mut EscrowFixture,
+    kind: OrderKind,
+    trigger_price: i64,
+    price: i64,
+) -> Pubkey {
+    let feed = Pubkey::new_unique();
+    let quote = MockPrice::new(&f.ctx.svm, price, -8);
+    write_price(&mut f.ctx.svm, feed, &quote);
+
+    let make_ix = f.make_ix(SEED, RECEIVE, AMOUNT);
+    let order_ix = place_order_ix(f, feed, kind, trigger_price);
+    let maker = f.maker.insecure_clone();
+    f.send(&[make_ix, order_ix], &[&maker]).unwrap();
+    feed
+}
+
+// Moves to a later slot and second, then publishes a fresh price
+fn next_slot_with_price(f: &mut EscrowFixture, feed: Pubkey, price: i64) {
+    let clock = f.ctx.svm.get_sysvar::<Clock>();
+    f.ctx.svm.warp_to_slot(clock.slot + 25);
+    set_unix_timestamp(&mut f.ctx.svm, clock.unix_timestamp + 10);
+    let quote = MockPrice::new(&f.ctx.svm, price, -8);
+    write_price(&mut f.ctx.svm, feed, &quote);
+}
+
+// The taker stands on the other side, selling RECEIVE of mint_b for `receive` of mint_a
+fn make_counter_escrow(f: &mut EscrowFixture, receive: u64) -> (Pubkey, Pubkey) {
+    let counter_maker = f.taker.insecure_clone();
+    f.init_registry(&counter_maker.pubkey()).unwrap();
+
+    let counter_seed = 7u64;
+    let counter_escrow = Pubkey::find_program_address(
+        &[
+            b"escrow",
+            counter_maker.pubkey().as_ref(),
+            &counter_seed.to_le_bytes(),
+        ],
+        &anchor_escrow::ID,
+    )
+    .0;
+    let counter_vault = get_associated_token_address(&counter_escrow, &f.mint_b.pubkey());
+
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::Make {
+            payer: counter_maker.pubkey(),
+            maker: counter_maker.pubkey(),
+            config: f.config(),
+            escrow: counter_escrow,
+            registry: f.registry(&counter_maker.pubkey()),
+            maker_stats: f.maker_stats(&counter_maker.pubkey()),
+            pair_stats: pair_stats_address(&f.mint_b.pubkey(), &f.mint_a.pubkey()),
+            mint_a: f.mint_b.pubkey(),
+            mint_b: f.mint_a.pubkey(),
+            mint_a_rule: mint_rule_address(&f.mint_b.pubkey()),
+            mint_b_rule: mint_rule_address(&f.mint_a.pubkey()),
+            maker_ata_a: f.taker_ata_b,
+            vault: counter_vault,
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::Make {
+            seed: counter_seed,
+            receive,
+            amount: RECEIVE,
+        }
+        .data(),
+    };
+    f.send(&[ix], &[&counter_maker]).unwrap();
+
+    (counter_escrow, counter_vault)
+}
+
+fn match_orders_ix(
+    f: &EscrowFixture,
+    keeper: &Pubkey,
+    counter_escrow: Pubkey,
+    counter_vault: Pubkey,
+) -> Instruction {
+    let counter_maker = f.taker.pubkey();
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::MatchOrders {
+            keeper: *keeper,
+            maker: f.maker.pubkey(),
+            config: f.config(),
+            escrow: f.escrow(SEED),
+            registry: f.registry(&f.maker.pubkey()),
+            maker_stats: f.maker_stats(&f.maker.pubkey()),
+            pair_stats: f.pair_stats(),
+            counter_maker,
+            counter_escrow,
+            counter_registry: f.registry(&counter_maker),
+            counter_maker_stats: f.maker_stats(&counter_maker),
+            counter_pair_stats: pair_stats_address(&f.mint_b.pubkey(), &f.mint_a.pubkey()),
+            mint_a: f.mint_a.pubkey(),
+            mint_b: f.mint_b.pubkey(),
+            vault: f.vault(SEED),
+            counter_vault,
+            maker_ata_b: get_associated_token_address(&f.maker.pubkey(), &f.mint_b.pubkey()),
+            counter_maker_ata_a: get_associated_token_address(&counter_maker, &f.mint_a.pubkey()),
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::MatchOrders {}.data(),
+    }
+}
+
+#[test]
+fn test_limit_order_triggers_when_price_rises() {
+    let mut f = EscrowFixture::new();
+    let keeper = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    let feed = make_conditional(&mut f, OrderKind::Limit, 60_000_000, 50_000_000);
+    assert!(f.escrow_state(SEED).trigger_pending());
+
+    // Below the trigger nothing can fill
+    assert_escrow_error(f.take(SEED), EscrowError::TriggerPending);
+    let ix = trigger_order_ix(&f, &keeper.pubkey(), feed);
+    assert_escrow_error(f.send(&[ix], &[&keeper]), EscrowError::TriggerNotReached);
+
+    next_slot_with_price(&mut f, feed, 59_999_999);
+    let ix = trigger_order_ix(&f, &keeper.pubkey(), feed);
+    assert_escrow_error(f.send(&[ix], &[&keeper]), EscrowError::TriggerNotReached);
+
+    next_slot_with_price(&mut f, feed, 60_000_000);
+    let keeper_before = f.ctx.svm.get_balance(&keeper.pubkey()).unwrap();
+    let ix = trigger_order_ix(&f, &keeper.pubkey(), feed);
+    let fee = 5_000;
+    f.send(&[ix], &[&keeper]).unwrap();
+
+    assert_eq!(
+        f.ctx.svm.get_balance(&keeper.pubkey()).unwrap(),
+        keeper_before + BOUNTY - fee
+    );
+    assert!(f.is_closed(&order(&f)));
+    assert!(!f.escrow_state(SEED).trigger_pending());
+
+    f.take(SEED).unwrap();
+}
+
+#[test]
+fn test_stop_order_triggers_when_price_falls() {
+    let mut f = EscrowFixture::new();
+    let keeper = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    let feed = make_conditional(&mut f, OrderKind::Stop, 40_000_000, 45_000_000);
+
+    let ix = trigger_order_ix(&f, &keeper.pubkey(), feed);
+    assert_escrow_error(f.send(&[ix], &[&keeper]), EscrowError::TriggerNotReached);
+
+    next_slot_with_price(&mut f, feed, 39_000_000);
+    let ix = trigger_order_ix(&f, &keeper.pubkey(), feed);
+    f.send(&[ix], &[&keeper]).unwrap();
+
+    f.take(SEED).unwrap();
+}
+
+#[test]
+fn test_triggered_order_stays_fillable_after_price_reverts() {
+    let mut f = EscrowFixture::new();
+    let keeper = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    let feed = make_conditional(&mut f, OrderKind::Limit, 60_000_000, 50_000_000);
+
+    next_slot_with_price(&mut f, feed, 61_000_000);
+    let ix = trigger_order_ix(&f, &keeper.pubkey(), feed);
+    f.send(&[ix], &[&keeper]).unwrap();
+
+    // Back below the trigger, the fill goes ahead at the fixed price
+    next_slot_with_price(&mut f, feed, 50_000_000);
+    f.take(SEED).unwrap();
+
+    let maker_ata_b = get_associated_token_address(&f.maker.pubkey(), &f.mint_b.pubkey());
+    assert_eq!(f.token_balance(&maker_ata_b), RECEIVE);
+}
+
+#[test]
+fn test_trigger_rejects_stale_or_foreign_feeds() {
+    let mut f = EscrowFixture::new();
+    let keeper = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    let feed = make_conditional(&mut f, OrderKind::Limit, 60_000_000, 70_000_000);
+
+    // A crossed price that is too old doesn't count
+    let now = f.ctx.svm.get_sysvar::<Clock>().unix_timestamp;
+    set_unix_timestamp(&mut f.ctx.svm, now + MAX_STALENESS as i64 + 1);
+    let ix = trigger_order_ix(&f, &keeper.pubkey(), feed);
+    assert_escrow_error(f.send(&[ix], &[&keeper]), EscrowError::StalePrice);
+
+    let other_feed = Pubkey::new_unique();
+    let quote = MockPrice::new(&f.ctx.svm, 70_000_000, -8);
+    write_price(&mut f.ctx.svm, other_feed, &quote);
+    let ix = trigger_order_ix(&f, &keeper.pubkey(), other_feed);
+    assert_escrow_error(f.send(&[ix], &[&keeper]), EscrowError::InvalidPriceFeed);
+}
+
+#[test]
+fn test_cancel_order_returns_bounty_and_unlocks_take() {
+    let mut f = EscrowFixture::new();
+    make_conditional(&mut f, OrderKind::Limit, 60_000_000, 50_000_000);
+    let maker_before = f.ctx.svm.get_balance(&f.maker.pubkey()).unwrap();
+    let order_lamports = f.ctx.svm.get_balance(&order(&f)).unwrap();
+
+    let ix = cancel_order_ix(&f, Some(f.escrow(SEED)), None);
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+
+    assert_eq!(
+        f.ctx.svm.get_balance(&f.maker.pubkey()).unwrap(),
+        maker_before + order_lamports - 5_000
+    );
+    f.take(SEED).unwrap();
+}
+
+#[test]
+fn test_cancel_order_needs_the_open_escrow() {
+    let mut f = EscrowFixture::new();
+    make_conditional(&mut f, OrderKind::Limit, 60_000_000, 50_000_000);
+    let maker = f.maker.insecure_clone();
+
+    let ix = cancel_order_ix(&f, None, None);
+    assert_escrow_error(f.send(&[ix], &[&maker]), EscrowError::EscrowAccountMissing);
+
+    // An open escrow doesn't pass for a refunded one
+    let ix = cancel_order_ix(&f, None, Some(f.escrow(SEED)));
+    assert!(f.send(&[ix], &[&maker]).is_err());
+    assert!(f.escrow_state(SEED).trigger_pending());
+}
+
+#[test]
+fn test_cancel_order_after_refund() {
+    let mut f = EscrowFixture::new();
+    make_conditional(&mut f, OrderKind::Limit, 60_000_000, 50_000_000);
+    f.refund(SEED).unwrap();
+
+    let ix = cancel_order_ix(&f, None, Some(f.escrow(SEED)));
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+
+    assert!(f.is_closed(&order(&f)));
+}
+
+#[test]
+fn test_keeper_matches_triggered_order_against_counter_escrow() {
+    let mut f = EscrowFixture::new();
+    let keeper = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    let feed = make_conditional(&mut f, OrderKind::Limit, 60_000_000, 50_000_000);
+    let (counter_escrow, counter_vault) = make_counter_escrow(&mut f, AMOUNT);
+    let match_ix = match_orders_ix(&f, &keeper.pubkey(), counter_escrow, counter_vault);
+
+    // Untriggered orders can't be matched
+    assert_escrow_error(
+        f.send(std::slice::from_ref(&match_ix), &[&keeper]),
+        EscrowError::TriggerPending,
+    );
+
+    // The keeper lands the trigger and the fill in one transaction
+    next_slot_with_price(&mut f, feed, 61_000_000);
+    let trigger_ix = trigger_order_ix(&f, &keeper.pubkey(), feed);
+    f.send(&[trigger_ix, match_ix], &[&keeper]).unwrap();
+
+    let maker_ata_b = get_associated_token_address(&f.maker.pubkey(), &f.mint_b.pubkey());
+    let counter_maker_ata_a = get_associated_token_address(&f.taker.pubkey(), &f.mint_a.pubkey());
+    assert_eq!(f.token_balance(&maker_ata_b), RECEIVE);
+    assert_eq!(f.token_balance(&counter_maker_ata_a), AMOUNT);
+    for closed in [f.escrow(SEED), f.vault(SEED), counter_escrow, counter_vault] {
+        assert!(f.is_closed(&closed));
+    }
+}
+
+#[test]
+fn test_match_counts_a_fill_on_both_sides() {
+    let mut f = EscrowFixture::new();
+    let keeper = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    f.make(SEED).unwrap();
+    let (counter_escrow, counter_vault) = make_counter_escrow(&mut f, AMOUNT);
+
+    let ix = match_orders_ix(&f, &keeper.pubkey(), counter_escrow, counter_vault);
+    f.send(&[ix], &[&keeper]).unwrap();
+
+    for maker in [f.maker.pubkey(), f.taker.pubkey()] {
+        assert_eq!(f.maker_stats_state(&maker).counts, counts(1, 1, 0));
+    }
+    let pair = f.pair_stats_state();
+    assert_eq!(pair.counts, counts(1, 1, 0));
+    assert_eq!(
+        (pair.volume_a, pair.volume_b),
+        (AMOUNT.into(), RECEIVE.into())
+    );
+
+    // The counter escrow sold mint_b into the reverse market
+    let address = pair_stats_address(&f.mint_b.pubkey(), &f.mint_a.pubkey());
+    let account = f.ctx.svm.get_account(&address).unwrap();
+    let counter_pair = PairStats::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(counter_pair.counts, counts(1, 1, 0));
+    assert_eq!(
+        (counter_pair.volume_a, counter_pair.volume_b),
+        (RECEIVE.into(), AMOUNT.into())
+    );
+}
+
+#[test]
+fn test_non_crossing_escrows_are_not_matched() {
+    let mut f = EscrowFixture::new();
+    let keeper = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    f.make(SEED).unwrap();
+
+    // The counter escrow asks for more mint_a than the vault holds
+    let (counter_escrow, counter_vault) = make_counter_escrow(&mut f, AMOUNT + 1);
+    let ix = match_orders_ix(&f, &keeper.pubkey(), counter_escrow, counter_vault);
+    assert_escrow_error(f.send(&[ix], &[&keeper]), EscrowError::OrdersDoNotCross);
+}
+
+#[test]
+fn test_pause_blocks_match_orders() {
+    let mut f = EscrowFixture::new();
+    let keeper = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    f.make(SEED).unwrap();
+    let (counter_escrow, counter_vault) = make_counter_escrow(&mut f, AMOUNT);
+    f.pause();
+
+    let ix = match_orders_ix(&f, &keeper.pubkey(), counter_escrow, counter_vault);
+    assert_escrow_error(f.send(&[ix], &[&keeper]), EscrowError::ProgramPaused);
+}
+
+#[test]
+fn test_pending_order_blocks_transfer() {
+    let mut f = EscrowFixture::new();
+    make_conditional(&mut f, OrderKind::Limit, 60_000_000, 50_000_000);
+    let maker = f.maker.insecure_clone();
+    let new_maker = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    let transfer_ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::TransferEscrow {
+            maker: maker.pubkey(),
+            new_maker: new_maker.pubkey(),
+            escrow: f.escrow(SEED),
+            registry: f.registry(&maker.pubkey()),
+            new_registry: f.registry(&new_maker.pubkey()),
+            new_maker_stats: f.maker_stats(&new_maker.pubkey()),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::TransferEscrow {}.data(),
+    };
+
+    // The order would stay cancellable only by the old maker
+    assert_escrow_error(
+        f.send(std::slice::from_ref(&transfer_ix), &[&maker]),
+        EscrowError::TriggerPending,
+    );
+
+    let cancel_ix = cancel_order_ix(&f, Some(f.escrow(SEED)), None);
+    f.send(&[cancel_ix, transfer_ix], &[&maker]).unwrap();
+    assert_eq!({ f.escrow_state(SEED).maker }, new_maker.pubkey());
+}
@@ -0,0 +1,203 @@
+use crate::common::{assert_escrow_error, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use anchor_escrow::errors::EscrowError;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::types::TransactionResult;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+};
+
+fn deposit_ix(
+    f: &EscrowFixture,
+    maker: &Keypair,
+    amount: u64,
+    receive: Option<u64>,
+) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::Deposit {
+            maker: maker.pubkey(),
+            config: f.config(),
+            escrow: f.escrow(SEED),
+            mint_a: f.mint_a.pubkey(),
+            vault: f.vault(SEED),
+            maker_ata_a: f.maker_ata_a,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::Deposit { amount, receive }.data(),
+    }
+}
+
+fn deposit(f: &mut EscrowFixture, amount: u64, receive: Option<u64>) -> TransactionResult {
+    let maker = f.maker.insecure_clone();
+    let ix = deposit_ix(f, &maker, amount, receive);
+    f.send(&[ix], &[&maker])
+}
+
+fn withdraw_as(
+    f: &mut EscrowFixture,
+    signer: &Keypair,
+    amount: u64,
+    receive: Option<u64>,
+) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::Withdraw {
+            maker: signer.pubkey(),
+            escrow: f.escrow(SEED),
+            mint_a: f.mint_a.pubkey(),
+            vault: f.vault(SEED),
+            maker_ata_a: f.maker_ata_a,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::Withdraw { amount, receive }.data(),
+    };
+    f.send(&[ix], &[signer])
+}
+
+fn withdraw(f: &mut EscrowFixture, amount: u64, receive: Option<u64>) -> TransactionResult {
+    let maker = f.maker.insecure_clone();
+    withdraw_as(f, &maker, amount, receive)
+}
+
+// Mints more mint_a to the maker, who is its mint authority
+fn fund_maker(f: &mut EscrowFixture, amount: u64) {
+    let ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &f.mint_a.pubkey(),
+        &f.maker_ata_a,
+        &f.maker.pubkey(),
+        &[],
+        amount,
+    )
+    .unwrap();
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+}
+
+fn receive(f: &EscrowFixture) -> u64 {
+    f.escrow_state(SEED).receive
+}
+
+#[test]
+fn test_deposit_scales_receive() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    fund_maker(&mut f, AMOUNT / 2);
+
+    deposit(&mut f, AMOUNT / 2, None).unwrap();
+
+    assert_eq!(f.token_balance(&f.vault(SEED)), AMOUNT * 3 / 2);
+    assert_eq!(receive(&f), RECEIVE * 3 / 2);
+}
+
+#[test]
+fn test_deposit_sets_explicit_receive() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    fund_maker(&mut f, AMOUNT);
+
+    deposit(&mut f, AMOUNT, Some(RECEIVE)).unwrap();
+
+    assert_eq!(f.token_balance(&f.vault(SEED)), AMOUNT * 2);
+    assert_eq!(receive(&f), RECEIVE);
+}
+
+#[test]
+fn test_withdraw_scales_receive() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+
+    withdraw(&mut f, AMOUNT / 4, None).unwrap();
+
+    assert_eq!(f.token_balance(&f.vault(SEED)), AMOUNT * 3 / 4);
+    assert_eq!(f.token_balance(&f.maker_ata_a), AMOUNT / 4);
+    assert_eq!(receive(&f), RECEIVE * 3 / 4);
+}
+
+#[test]
+fn test_scaled_receive_rounds_up() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+
+    // 0.5 * (1e9 - 1) / 1e9 tokens is half a unit short of RECEIVE
+    withdraw(&mut f, 1, None).unwrap();
+
+    assert_eq!(receive(&f), RECEIVE);
+}
+
+#[test]
+fn test_withdraw_to_zero_leaves_an_untakeable_escrow() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+
+    withdraw(&mut f, AMOUNT, None).unwrap();
+
+    // The escrow stays open, tracked and priced, with an empty vault
+    assert_eq!(f.token_balance(&f.vault(SEED)), 0);
+    assert_eq!(f.token_balance(&f.maker_ata_a), AMOUNT);
+    assert_eq!(receive(&f), RECEIVE);
+    assert_escrow_error(f.take(SEED), EscrowError::EmptyVault);
+}
+
+#[test]
+fn test_emptied_escrow_refills_at_an_explicit_price() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    withdraw(&mut f, AMOUNT, None).unwrap();
+
+    assert_escrow_error(deposit(&mut f, AMOUNT, None), EscrowError::ReceiveRequired);
+    deposit(&mut f, AMOUNT, Some(RECEIVE / 2)).unwrap();
+    assert_eq!(receive(&f), RECEIVE / 2);
+
+    f.take(SEED).unwrap();
+    assert!(f.is_closed(&f.escrow(SEED)));
+}
+
+#[test]
+fn test_emptied_escrow_can_still_be_refunded() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    withdraw(&mut f, AMOUNT, None).unwrap();
+
+    f.refund(SEED).unwrap();
+
+    assert!(f.is_closed(&f.escrow(SEED)));
+    assert!(f.is_closed(&f.vault(SEED)));
+    assert_eq!(f.token_balance(&f.maker_ata_a), AMOUNT);
+}
+
+#[test]
+fn test_withdraw_cannot_exceed_vault() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+
+    assert_escrow_error(
+        withdraw(&mut f, AMOUNT + 1, None),
+        EscrowError::InvalidAmount,
+    );
+}
+
+#[test]
+fn test_only_maker_can_withdraw() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    let taker = f.taker.insecure_clone();
+
+    assert_escrow_error(
+        withdraw_as(&mut f, &taker, AMOUNT, None),
+        EscrowError::InvalidMaker,
+    );
+}
+
+#[test]
+fn test_pause_blocks_deposit() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    fund_maker(&mut f, AMOUNT);
+    f.pause();
+
+    assert_escrow_error(deposit(&mut f, AMOUNT, None), EscrowError::ProgramPaused);
+}
@@ -0,0 +1,309 @@
+use crate::common::{
+    assert_escrow_error, mint_rule_address, take_instruction, EscrowFixture, AMOUNT, RECEIVE, SEED,
+};
+use anchor_escrow::errors::EscrowError;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_litesvm::TestHelpers;
+use litesvm::types::TransactionResult;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    sysvar,
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
+
+fn deploy_router(f: &mut EscrowFixture) {
+    f.ctx.svm.add_program(
+        escrow_router::ID,
+        include_bytes!("../../target/deploy/escrow_router.so"),
+    );
+}
+
+fn desk(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"desk", owner.as_ref()], &escrow_router::ID).0
+}
+
+fn desk_escrow(desk: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"escrow", desk.as_ref(), &SEED.to_le_bytes()],
+        &anchor_escrow::ID,
+    )
+    .0
+}
+
+// Opens the desk's token account for `mint` and funds it with `amount`
+fn fund_desk(f: &mut EscrowFixture, desk: &Pubkey, mint: &Pubkey, amount: u64) {
+    let ata = get_associated_token_address(desk, mint);
+    let create_ix =
+        create_associated_token_account(&f.maker.pubkey(), desk, mint, &spl_token::id());
+    let mint_ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        mint,
+        &ata,
+        &f.maker.pubkey(),
+        &[],
+        amount,
+    )
+    .unwrap();
+    let maker = f.maker.insecure_clone();
+    f.send(&[create_ix, mint_ix], &[&maker]).unwrap();
+}
+
+fn desk_lamports(f: &EscrowFixture, desk: &Pubkey) -> u64 {
+    f.ctx.svm.get_balance(desk).unwrap_or(0)
+}
+
+fn route_make(f: &mut EscrowFixture, owner: &Keypair) {
+    let desk = desk(&owner.pubkey());
+    let escrow = desk_escrow(&desk);
+    let ix = Instruction {
+        program_id: escrow_router::ID,
+        accounts: escrow_router::accounts::RouteMake {
+            owner: owner.pubkey(),
+            desk,
+            config: f.config(),
+            escrow,
+            registry: f.registry(&desk),
+            maker_stats: f.maker_stats(&desk),
+            pair_stats: f.pair_stats(),
+            mint_a: f.mint_a.pubkey(),
+            mint_b: f.mint_b.pubkey(),
+            mint_a_rule: mint_rule_address(&f.mint_a.pubkey()),
+            mint_b_rule: mint_rule_address(&f.mint_b.pubkey()),
+            desk_ata_a: get_associated_token_address(&desk, &f.mint_a.pubkey()),
+            vault: get_associated_token_address(&escrow, &f.mint_a.pubkey()),
+            escrow_program: anchor_escrow::ID,
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: escrow_router::instruction::RouteMake {
+            seed: SEED,
+            receive: RECEIVE,
+            amount: AMOUNT,
+        }
+        .data(),
+    };
+    f.send(&[ix], &[owner]).unwrap();
+}
+
+fn route_take(
+    f: &mut EscrowFixture,
+    owner: &Keypair,
+    instructions_sysvar: Option<Pubkey>,
+) -> TransactionResult {
+    let desk = desk(&owner.pubkey());
+    let maker = f.maker.pubkey();
+    let ix = Instruction {
+        program_id: escrow_router::ID,
+        accounts: escrow_router::accounts::RouteTake {
+            owner: owner.pubkey(),
+            desk,
+            maker,
+            config: f.config(),
+            escrow: f.escrow(SEED),
+            registry: f.registry(&maker),
+            maker_stats: f.maker_stats(&maker),
+            pair_stats: f.pair_stats(),
+            mint_a: f.mint_a.pubkey(),
+            mint_b: f.mint_b.pubkey(),
+            vault: f.vault(SEED),
+            desk_ata_a: get_associated_token_address(&desk, &f.mint_a.pubkey()),
+            desk_ata_b: get_associated_token_address(&desk, &f.mint_b.pubkey()),
+            maker_ata_b: get_associated_token_address(&maker, &f.mint_b.pubkey()),
+            escrow_program: anchor_escrow::ID,
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+            instructions_sysvar,
+        }
+        .to_account_metas(None),
+        data: escrow_router::instruction::RouteTake {
+            max_receive: RECEIVE,
+        }
+        .data(),
+    };
+    f.send(&[ix], &[owner])
+}
+
+fn route_refund(f: &mut EscrowFixture, owner: &Keypair) -> TransactionResult {
+    let desk = desk(&owner.pubkey());
+    let escrow = desk_escrow(&desk);
+    let ix = Instruction {
+        program_id: escrow_router::ID,
+        accounts: escrow_router::accounts::RouteRefund {
+            owner: owner.pubkey(),
+            desk,
+            escrow,
+            registry: f.registry(&desk),
+            maker_stats: f.maker_stats(&desk),
+            pair_stats: f.pair_stats(),
+            mint_a: f.mint_a.pubkey(),
+            vault: get_associated_token_address(&escrow, &f.mint_a.pubkey()),
+            desk_ata_a: get_associated_token_address(&desk, &f.mint_a.pubkey()),
+            escrow_program: anchor_escrow::ID,
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: escrow_router::instruction::RouteRefund {}.data(),
+    };
+    f.send(&[ix], &[owner])
+}
+
+// Withdraws `amount` of `mint` from `desk`, signed by `owner`
+fn withdraw_from_desk(
+    f: &mut EscrowFixture,
+    owner: &Keypair,
+    desk: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> TransactionResult {
+    let ix = Instruction {
+        program_id: escrow_router::ID,
+        accounts: escrow_router::accounts::WithdrawFromDesk {
+            owner: owner.pubkey(),
+            desk: *desk,
+            mint: *mint,
+            desk_ata: get_associated_token_address(desk, mint),
+            owner_ata: get_associated_token_address(&owner.pubkey(), mint),
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: escrow_router::instruction::WithdrawFromDesk { amount }.data(),
+    };
+    f.send(&[ix], &[owner])
+}
+
+#[test]
+fn test_router_made_escrow_is_taken_directly() {
+    let mut f = EscrowFixture::new();
+    deploy_router(&mut f);
+    let owner = f.maker.insecure_clone();
+    let desk = desk(&owner.pubkey());
+    f.init_registry(&desk).unwrap();
+    let mint_a = f.mint_a.pubkey();
+    fund_desk(&mut f, &desk, &mint_a, AMOUNT);
+
+    route_make(&mut f, &owner);
+    let escrow = desk_escrow(&desk);
+    let vault = get_associated_token_address(&escrow, &mint_a);
+    assert_eq!(f.token_balance(&vault), AMOUNT);
+    // The owner paid every rent, so the desk still has no lamports
+    assert_eq!(desk_lamports(&f, &desk), 0);
+
+    let mut accounts = f.take_accounts(SEED, &f.taker.pubkey(), &desk);
+    accounts.escrow = escrow;
+    accounts.vault = vault;
+    let ix = take_instruction(accounts, vec![]);
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker]).unwrap();
+
+    assert!(f.is_closed(&escrow));
+    let desk_ata_b = get_associated_token_address(&desk, &f.mint_b.pubkey());
+    assert_eq!(f.token_balance(&desk_ata_b), RECEIVE);
+}
+
+#[test]
+fn test_router_takes_a_direct_escrow() {
+    let mut f = EscrowFixture::new();
+    deploy_router(&mut f);
+    f.make(SEED).unwrap();
+    let owner = f.taker.insecure_clone();
+    let desk = desk(&owner.pubkey());
+    let mint_b = f.mint_b.pubkey();
+    fund_desk(&mut f, &desk, &mint_b, RECEIVE);
+
+    route_take(&mut f, &owner, None).unwrap();
+
+    assert!(f.is_closed(&f.escrow(SEED)));
+    let desk_ata_a = get_associated_token_address(&desk, &f.mint_a.pubkey());
+    assert_eq!(f.token_balance(&desk_ata_a), AMOUNT);
+    let maker_ata_b = get_associated_token_address(&f.maker.pubkey(), &mint_b);
+    assert_eq!(f.token_balance(&maker_ata_b), RECEIVE);
+    assert_eq!(desk_lamports(&f, &desk), 0);
+}
+
+#[test]
+fn test_router_cannot_fill_a_solo_take_escrow() {
+    let mut f = EscrowFixture::new();
+    deploy_router(&mut f);
+    let make_ix = f.make_ix(SEED, RECEIVE, AMOUNT);
+    let solo_ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetSoloTake {
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetSoloTake { enabled: true }.data(),
+    };
+    let maker = f.maker.insecure_clone();
+    f.send(&[make_ix, solo_ix], &[&maker]).unwrap();
+    let owner = f.taker.insecure_clone();
+    let desk = desk(&owner.pubkey());
+    let mint_b = f.mint_b.pubkey();
+    fund_desk(&mut f, &desk, &mint_b, RECEIVE);
+
+    assert_escrow_error(
+        route_take(&mut f, &owner, Some(sysvar::instructions::ID)),
+        EscrowError::TakeViaCpi,
+    );
+    assert_escrow_error(
+        route_take(&mut f, &owner, None),
+        EscrowError::InstructionsSysvarMissing,
+    );
+}
+
+#[test]
+fn test_router_refunds_a_desk_escrow() {
+    let mut f = EscrowFixture::new();
+    deploy_router(&mut f);
+    let owner = f.maker.insecure_clone();
+    let desk = desk(&owner.pubkey());
+    let mint_a = f.mint_a.pubkey();
+    fund_desk(&mut f, &desk, &mint_a, AMOUNT);
+    route_make(&mut f, &owner);
+    let owner_before = f.ctx.svm.get_balance(&owner.pubkey()).unwrap();
+
+    route_refund(&mut f, &owner).unwrap();
+
+    assert!(f.is_closed(&desk_escrow(&desk)));
+    let desk_ata_a = get_associated_token_address(&desk, &mint_a);
+    assert_eq!(f.token_balance(&desk_ata_a), AMOUNT);
+    // The escrow and vault rent went back to the owner who paid it
+    assert_eq!(desk_lamports(&f, &desk), 0);
+    assert!(f.ctx.svm.get_balance(&owner.pubkey()).unwrap() > owner_before);
+}
+
+#[test]
+fn test_only_the_owner_withdraws_from_their_desk() {
+    let mut f = EscrowFixture::new();
+    deploy_router(&mut f);
+    let owner = f.taker.insecure_clone();
+    let desk = desk(&owner.pubkey());
+    let mint_a = f.mint_a.pubkey();
+    let mint_b = f.mint_b.pubkey();
+    f.make(SEED).unwrap();
+    fund_desk(&mut f, &desk, &mint_b, RECEIVE);
+    route_take(&mut f, &owner, None).unwrap();
+
+    let stranger = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    assert!(withdraw_from_desk(&mut f, &stranger, &desk, &mint_a, AMOUNT).is_err());
+
+    // Opens the owner's mint_a account on the way
+    withdraw_from_desk(&mut f, &owner, &desk, &mint_a, AMOUNT).unwrap();
+    let owner_ata_a = get_associated_token_address(&owner.pubkey(), &mint_a);
+    assert_eq!(f.token_balance(&owner_ata_a), AMOUNT);
+    assert_eq!(
+        f.token_balance(&get_associated_token_address(&desk, &mint_a)),
+        0
+    );
+}
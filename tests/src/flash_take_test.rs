@@ -0,0 +1,142 @@
+use crate::common::{assert_escrow_error, counts, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use anchor_escrow::errors::EscrowError;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::types::TransactionResult;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+fn deploy_callback(f: &mut EscrowFixture) {
+    f.ctx.svm.add_program(
+        flash_callback::ID,
+        include_bytes!("../../target/deploy/flash_callback.so"),
+    );
+}
+
+fn maker_ata_b(f: &EscrowFixture) -> Pubkey {
+    get_associated_token_address(&f.maker.pubkey(), &f.mint_b.pubkey())
+}
+
+fn taker_ata_a(f: &EscrowFixture) -> Pubkey {
+    get_associated_token_address(&f.taker.pubkey(), &f.mint_a.pubkey())
+}
+
+// Flash-takes SEED, with the sample callback paying `repay` of mint_b to the maker
+fn flash_take(f: &mut EscrowFixture, callback_program: Pubkey, repay: u64) -> TransactionResult {
+    let mut accounts = anchor_escrow::accounts::FlashTake {
+        taker: f.taker.pubkey(),
+        maker: f.maker.pubkey(),
+        config: f.config(),
+        escrow: f.escrow(SEED),
+        registry: f.registry(&f.maker.pubkey()),
+        maker_stats: f.maker_stats(&f.maker.pubkey()),
+        pair_stats: f.pair_stats(),
+        mint_a: f.mint_a.pubkey(),
+        mint_b: f.mint_b.pubkey(),
+        vault: f.vault(SEED),
+        taker_ata_a: taker_ata_a(f),
+        maker_ata_b: maker_ata_b(f),
+        callback_program,
+        associated_token_program: spl_associated_token_account::id(),
+        token_program: spl_token::id(),
+        system_program: anchor_lang::system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        flash_callback::accounts::Repay {
+            taker: f.taker.pubkey(),
+            mint_b: f.mint_b.pubkey(),
+            taker_ata_b: f.taker_ata_b,
+            maker_ata_b: maker_ata_b(f),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+    );
+
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts,
+        data: anchor_escrow::instruction::FlashTake {
+            callback_data: flash_callback::instruction::Repay { amount: repay }.data(),
+        }
+        .data(),
+    };
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+#[test]
+fn test_flash_take_with_honest_callback() {
+    let mut f = EscrowFixture::new();
+    deploy_callback(&mut f);
+    f.make(SEED).unwrap();
+
+    flash_take(&mut f, flash_callback::ID, RECEIVE).unwrap();
+
+    assert!(f.is_closed(&f.escrow(SEED)));
+    assert!(f.is_closed(&f.vault(SEED)));
+    assert_eq!(f.token_balance(&taker_ata_a(&f)), AMOUNT);
+    assert_eq!(f.token_balance(&maker_ata_b(&f)), RECEIVE);
+    assert_eq!(f.token_balance(&f.taker_ata_b), 0);
+}
+
+#[test]
+fn test_flash_take_counts_as_a_fill() {
+    let mut f = EscrowFixture::new();
+    deploy_callback(&mut f);
+    f.make(SEED).unwrap();
+
+    flash_take(&mut f, flash_callback::ID, RECEIVE).unwrap();
+
+    let maker_stats = f.maker_stats_state(&f.maker.pubkey());
+    assert_eq!(maker_stats.counts, counts(1, 1, 0));
+    let pair = f.pair_stats_state();
+    assert_eq!(pair.counts, counts(1, 1, 0));
+    assert_eq!(
+        (pair.volume_a, pair.volume_b),
+        (AMOUNT.into(), RECEIVE.into())
+    );
+    assert_eq!((pair.last_fill_a, pair.last_fill_b), (AMOUNT, RECEIVE));
+}
+
+#[test]
+fn test_flash_take_reverts_when_callback_short_pays() {
+    let mut f = EscrowFixture::new();
+    deploy_callback(&mut f);
+    f.make(SEED).unwrap();
+
+    for repay in [0, RECEIVE - 1] {
+        assert_escrow_error(
+            flash_take(&mut f, flash_callback::ID, repay),
+            EscrowError::FlashRepaymentMissing,
+        );
+    }
+
+    // The whole transaction rolled back, vault payout included
+    assert!(!f.is_closed(&f.escrow(SEED)));
+    assert_eq!(f.token_balance(&f.vault(SEED)), AMOUNT);
+    assert_eq!(f.token_balance(&f.taker_ata_b), RECEIVE);
+}
+
+#[test]
+fn test_flash_take_rejects_escrow_program_as_callback() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+
+    assert_escrow_error(
+        flash_take(&mut f, anchor_escrow::ID, RECEIVE),
+        EscrowError::InvalidCallbackProgram,
+    );
+}
+
+#[test]
+fn test_pause_blocks_flash_take() {
+    let mut f = EscrowFixture::new();
+    deploy_callback(&mut f);
+    f.make(SEED).unwrap();
+    f.pause();
+
+    assert_escrow_error(
+        flash_take(&mut f, flash_callback::ID, RECEIVE),
+        EscrowError::ProgramPaused,
+    );
+}
@@ -5,4 +5,88 @@ mod regular_litesvm_test;
 mod anchor_litesvm_test;
 
 #[cfg(test)]
-mod anchor_client_with_litesvm_test;
\ No newline at end of file
+mod anchor_client_with_litesvm_test;
+
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod transfer_escrow_test;
+
+#[cfg(test)]
+mod maker_registry_test;
+
+#[cfg(test)]
+mod merkle_tree;
+
+#[cfg(test)]
+mod allowlist_test;
+
+#[cfg(test)]
+mod token_gate_test;
+
+#[cfg(test)]
+mod mock_oracle;
+
+#[cfg(test)]
+mod oracle_pricing_test;
+
+#[cfg(test)]
+mod conditional_order_test;
+
+#[cfg(test)]
+mod flash_take_test;
+
+#[cfg(test)]
+mod vesting_test;
+
+#[cfg(test)]
+mod tranche_test;
+
+#[cfg(test)]
+mod reservation_test;
+
+#[cfg(test)]
+mod rfq_test;
+
+#[cfg(test)]
+mod multisig_test;
+
+#[cfg(test)]
+mod escrow_router_test;
+
+#[cfg(test)]
+mod stats_test;
+
+#[cfg(test)]
+mod solo_take_test;
+
+#[cfg(test)]
+mod migration_test;
+
+#[cfg(test)]
+mod compute_budget_test;
+
+#[cfg(test)]
+mod pda_vault_test;
+
+#[cfg(test)]
+mod take_cleanup_test;
+
+#[cfg(test)]
+mod make_validation_test;
+
+#[cfg(test)]
+mod pause_test;
+
+#[cfg(test)]
+mod mint_rules_test;
+
+#[cfg(test)]
+mod referral_test;
+
+#[cfg(test)]
+mod deposit_withdraw_test;
+
+#[cfg(test)]
+mod start_time_test;
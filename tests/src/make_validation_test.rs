@@ -0,0 +1,112 @@
+use crate::common::{
+    assert_escrow_error, make_instruction, pair_stats_address, EscrowFixture, AMOUNT, RECEIVE, SEED,
+};
+use anchor_escrow::errors::EscrowError;
+use litesvm::types::TransactionResult;
+use solana_program_pack::Pack;
+use solana_sdk::{
+    account::Account,
+    program_option::COption,
+    pubkey,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_token::state::{AccountState, Mint};
+
+const TOKEN_2022_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+// Anchor numbers custom errors from 6000 in declaration order, and clients
+// match on the number, so it is pinned here as well as the variant
+fn assert_error_number(result: TransactionResult, error: EscrowError, number: u32) {
+    assert_eq!(u32::from(error), number);
+    assert_escrow_error(result, error);
+}
+
+fn send_make(
+    f: &mut EscrowFixture,
+    accounts: anchor_escrow::accounts::Make,
+    amount: u64,
+) -> TransactionResult {
+    let ix = make_instruction(accounts, SEED, RECEIVE, amount);
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker])
+}
+
+#[test]
+fn test_make_rejects_same_mint() {
+    let mut f = EscrowFixture::new();
+    let mut accounts = f.make_accounts(SEED);
+    accounts.mint_b = f.mint_a.pubkey();
+    accounts.pair_stats = pair_stats_address(&f.mint_a.pubkey(), &f.mint_a.pubkey());
+
+    let result = send_make(&mut f, accounts, AMOUNT);
+    assert_error_number(result, EscrowError::SameMint, 6053);
+}
+
+#[test]
+fn test_make_rejects_mint_of_another_token_program() {
+    let mut f = EscrowFixture::new();
+    // A plain mint owned by Token-2022, while make is called with SPL Token
+    let mint = Mint {
+        decimals: 9,
+        is_initialized: true,
+        mint_authority: COption::Some(f.maker.pubkey()),
+        ..Default::default()
+    };
+    let mut data = vec![0; Mint::LEN];
+    Mint::pack(mint, &mut data).unwrap();
+    let mint_b = Keypair::new().pubkey();
+    let lamports = f.ctx.svm.minimum_balance_for_rent_exemption(Mint::LEN);
+    f.ctx
+        .svm
+        .set_account(
+            mint_b,
+            Account {
+                lamports,
+                data,
+                owner: TOKEN_2022_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    let mut accounts = f.make_accounts(SEED);
+    accounts.mint_b = mint_b;
+    accounts.pair_stats = pair_stats_address(&f.mint_a.pubkey(), &mint_b);
+
+    let result = send_make(&mut f, accounts, AMOUNT);
+    assert_error_number(result, EscrowError::MintTokenProgramMismatch, 6054);
+}
+
+#[test]
+fn test_make_rejects_frozen_maker_account() {
+    let mut f = EscrowFixture::new();
+    let mut account = f.ctx.svm.get_account(&f.maker_ata_a).unwrap();
+    let mut token_account = spl_token::state::Account::unpack(&account.data).unwrap();
+    token_account.state = AccountState::Frozen;
+    spl_token::state::Account::pack(token_account, &mut account.data).unwrap();
+    f.ctx.svm.set_account(f.maker_ata_a, account).unwrap();
+
+    let accounts = f.make_accounts(SEED);
+    let result = send_make(&mut f, accounts, AMOUNT);
+    assert_error_number(result, EscrowError::MakerAccountFrozen, 6055);
+}
+
+#[test]
+fn test_make_rejects_amount_above_balance() {
+    let mut f = EscrowFixture::new();
+    let accounts = f.make_accounts(SEED);
+
+    let result = send_make(&mut f, accounts, AMOUNT + 1);
+    assert_error_number(result, EscrowError::InsufficientMakerBalance, 6056);
+}
+
+#[test]
+fn test_make_accepts_exact_balance() {
+    let mut f = EscrowFixture::new();
+    let accounts = f.make_accounts(SEED);
+
+    send_make(&mut f, accounts, AMOUNT).unwrap();
+    assert_eq!(f.token_balance(&f.vault(SEED)), AMOUNT);
+}
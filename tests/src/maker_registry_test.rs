@@ -0,0 +1,115 @@
+use crate::common::{assert_escrow_error, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use anchor_escrow::{
+    errors::EscrowError,
+    state::{MakerRegistry, MakerStats},
+};
+use anchor_lang::AccountDeserialize;
+use solana_sdk::signature::Signer;
+
+fn registry_state(f: &EscrowFixture) -> MakerRegistry {
+    let account = f
+        .ctx
+        .svm
+        .get_account(&f.registry(&f.maker.pubkey()))
+        .unwrap();
+    MakerRegistry::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+// Splits the maker's balance over three escrows with seeds 1, 2 and 3
+fn make_three(f: &mut EscrowFixture) {
+    let maker = f.maker.insecure_clone();
+    for seed in 1..=3 {
+        let ix = f.make_ix(seed, RECEIVE / 4, AMOUNT / 4);
+        f.send(&[ix], &[&maker]).unwrap();
+    }
+}
+
+#[test]
+fn test_registry_tracks_make_take_and_refund() {
+    let mut f = EscrowFixture::new();
+    make_three(&mut f);
+
+    let registry = registry_state(&f);
+    assert_eq!(registry.maker, f.maker.pubkey());
+    assert_eq!(
+        registry.escrows,
+        vec![f.escrow(1), f.escrow(2), f.escrow(3)]
+    );
+    let registry_len = f
+        .ctx
+        .svm
+        .get_account(&f.registry(&f.maker.pubkey()))
+        .unwrap()
+        .data
+        .len();
+    assert_eq!(registry_len, MakerRegistry::space(3));
+
+    f.take(1).unwrap();
+    f.refund(3).unwrap();
+
+    assert_eq!(registry_state(&f).escrows, vec![f.escrow(2)]);
+    let registry_len = f
+        .ctx
+        .svm
+        .get_account(&f.registry(&f.maker.pubkey()))
+        .unwrap()
+        .data
+        .len();
+    assert_eq!(registry_len, MakerRegistry::space(1));
+}
+
+#[test]
+fn test_refund_all_cancels_every_listed_escrow() {
+    let mut f = EscrowFixture::new();
+    make_three(&mut f);
+    assert_eq!(f.token_balance(&f.maker_ata_a), AMOUNT - 3 * (AMOUNT / 4));
+
+    let ix = f.refund_all_ix(&[1, 2, 3]);
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+
+    for seed in 1..=3 {
+        assert!(f.is_closed(&f.escrow(seed)));
+        assert!(f.is_closed(&f.vault(seed)));
+    }
+    assert_eq!(f.token_balance(&f.maker_ata_a), AMOUNT);
+    assert!(registry_state(&f).escrows.is_empty());
+}
+
+#[test]
+fn test_refund_all_rejects_accounts_out_of_registry_order() {
+    let mut f = EscrowFixture::new();
+    make_three(&mut f);
+    let maker = f.maker.insecure_clone();
+
+    let ix = f.refund_all_ix(&[1, 3, 2]);
+    assert_escrow_error(f.send(&[ix], &[&maker]), EscrowError::RegistryMismatch);
+
+    let ix = f.refund_all_ix(&[1, 2]);
+    assert_escrow_error(f.send(&[ix], &[&maker]), EscrowError::RegistryMismatch);
+
+    assert_eq!(registry_state(&f).escrows.len(), 3);
+}
+
+#[test]
+fn test_first_make_creates_registry_and_stats() {
+    let mut f = EscrowFixture::without_registry();
+    assert!(f.is_closed(&f.registry(&f.maker.pubkey())));
+
+    f.make(SEED).unwrap();
+
+    let registry = registry_state(&f);
+    assert_eq!(registry.maker, f.maker.pubkey());
+    assert_eq!(registry.escrows, vec![f.escrow(SEED)]);
+    let account = f
+        .ctx
+        .svm
+        .get_account(&f.maker_stats(&f.maker.pubkey()))
+        .unwrap();
+    let stats = MakerStats::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(stats.maker, f.maker.pubkey());
+    assert_eq!(stats.counts.created, 1);
+
+    // Which take and refund then find like any others
+    f.take(SEED).unwrap();
+}
@@ -0,0 +1,74 @@
+use sha2::{Digest, Sha256};
+use solana_sdk::pubkey::Pubkey;
+
+// Off-chain counterpart of `anchor_escrow::merkle`, built on sha2 so the tests
+// don't just replay the program's own hashing code.
+
+fn leaf(key: &Pubkey) -> [u8; 32] {
+    Sha256::new()
+        .chain_update([0u8])
+        .chain_update(key)
+        .finalize()
+        .into()
+}
+
+fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    Sha256::new()
+        .chain_update([1u8])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+pub struct MerkleTree {
+    keys: Vec<Pubkey>,
+    // levels[0] holds the leaves, the last level holds the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(keys: &[Pubkey]) -> Self {
+        assert!(!keys.is_empty(), "a Merkle tree needs at least one key");
+
+        let mut levels = vec![keys.iter().map(leaf).collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            // An odd node out is promoted to the next level unchanged
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self {
+            keys: keys.to_vec(),
+            levels,
+        }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().unwrap()[0]
+    }
+
+    pub fn proof(&self, key: &Pubkey) -> Option<Vec<[u8; 32]>> {
+        let mut index = self.keys.iter().position(|k| k == key)?;
+        let mut proof = Vec::new();
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+}
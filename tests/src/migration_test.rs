@@ -0,0 +1,176 @@
+use crate::common::{assert_escrow_error, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use anchor_escrow::{
+    errors::EscrowError,
+    migration::{decode_escrow, EscrowV1, ESCROW_VERSION, LEGACY_DISCRIMINATOR},
+    state::{Escrow, MakerRegistry},
+};
+use anchor_lang::{
+    AccountDeserialize, AnchorSerialize, Discriminator, InstructionData, ToAccountMetas,
+};
+use litesvm::types::TransactionResult;
+use solana_sdk::{account::Account, instruction::Instruction, pubkey::Pubkey, signature::Signer};
+use spl_associated_token_account::instruction::create_associated_token_account;
+
+fn v1_escrow(f: &EscrowFixture) -> EscrowV1 {
+    let (_, bump) = Pubkey::find_program_address(
+        &[b"escrow", f.maker.pubkey().as_ref(), &SEED.to_le_bytes()],
+        &anchor_escrow::ID,
+    );
+    EscrowV1 {
+        seed: SEED,
+        maker: f.maker.pubkey(),
+        mint_a: f.mint_a.pubkey(),
+        mint_b: f.mint_b.pubkey(),
+        receive: RECEIVE,
+        bump,
+    }
+}
+
+fn legacy_bytes(escrow: &EscrowV1) -> Vec<u8> {
+    let mut data = LEGACY_DISCRIMINATOR.to_vec();
+    escrow.serialize(&mut data).unwrap();
+    data
+}
+
+// What a legacy escrow should read as once migrated
+fn migrated(f: &EscrowFixture, mut escrow: Escrow) -> Escrow {
+    let (_, vault_bump) = Pubkey::find_program_address(
+        &[
+            f.escrow(SEED).as_ref(),
+            spl_token::id().as_ref(),
+            f.mint_a.pubkey().as_ref(),
+        ],
+        &spl_associated_token_account::id(),
+    );
+    escrow.vault_bump = vault_bump;
+    escrow
+}
+
+fn write_escrow_account(f: &mut EscrowFixture, data: Vec<u8>) {
+    let account = Account {
+        lamports: f.ctx.svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: anchor_escrow::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    f.ctx.svm.set_account(f.escrow(SEED), account).unwrap();
+}
+
+// An escrow as the original program left it: v1 bytes, a funded vault and no
+// registry entry
+fn write_v1_escrow(f: &mut EscrowFixture) {
+    let data = legacy_bytes(&v1_escrow(f));
+    write_escrow_account(f, data);
+
+    let vault = f.vault(SEED);
+    let create_ix = create_associated_token_account(
+        &f.maker.pubkey(),
+        &f.escrow(SEED),
+        &f.mint_a.pubkey(),
+        &spl_token::id(),
+    );
+    let fund_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &f.maker_ata_a,
+        &vault,
+        &f.maker.pubkey(),
+        &[],
+        AMOUNT,
+    )
+    .unwrap();
+    let maker = f.maker.insecure_clone();
+    f.send(&[create_ix, fund_ix], &[&maker]).unwrap();
+}
+
+fn migrate(f: &mut EscrowFixture) -> TransactionResult {
+    let maker = f.maker.pubkey();
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::MigrateEscrow {
+            payer: f.taker.pubkey(),
+            maker,
+            escrow: f.escrow(SEED),
+            registry: f.registry(&maker),
+            maker_stats: f.maker_stats(&maker),
+            mint_a: f.mint_a.pubkey(),
+            mint_b: f.mint_b.pubkey(),
+            pair_stats: f.pair_stats(),
+            vault: f.vault(SEED),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::MigrateEscrow {}.data(),
+    };
+    // Migration is permissionless, so the taker pays for it here
+    let payer = f.taker.insecure_clone();
+    f.send(&[ix], &[&payer])
+}
+
+#[test]
+fn test_v1_escrow_migrates_in_place() {
+    let mut f = EscrowFixture::new();
+    write_v1_escrow(&mut f);
+    migrate(&mut f).unwrap();
+
+    let account = f.ctx.svm.get_account(&f.escrow(SEED)).unwrap();
+    assert_eq!(account.data.len(), Escrow::SPACE);
+    assert_eq!(f.escrow_state(SEED), migrated(&f, v1_escrow(&f).upgrade()));
+    assert_eq!(f.escrow_state(SEED).version, ESCROW_VERSION);
+    assert_eq!(f.escrow_state(SEED).creator, f.maker.pubkey());
+
+    let registry = f
+        .ctx
+        .svm
+        .get_account(&f.registry(&f.maker.pubkey()))
+        .unwrap();
+    let registry = MakerRegistry::try_deserialize(&mut registry.data.as_slice()).unwrap();
+    assert_eq!(registry.escrows, vec![f.escrow(SEED)]);
+}
+
+#[test]
+fn test_migrated_escrow_can_be_taken() {
+    let mut f = EscrowFixture::new();
+    write_v1_escrow(&mut f);
+    migrate(&mut f).unwrap();
+
+    f.take(SEED).unwrap();
+    assert!(f.is_closed(&f.escrow(SEED)));
+    assert_eq!(f.token_balance(&f.taker_ata_b), 0);
+}
+
+#[test]
+fn test_decoding_accepts_every_version() {
+    let mut f = EscrowFixture::new();
+    let v1 = v1_escrow(&f);
+    assert_eq!(decode_escrow(&legacy_bytes(&v1)).unwrap(), v1.upgrade());
+
+    f.make(SEED).unwrap();
+    let current = f.ctx.svm.get_account(&f.escrow(SEED)).unwrap();
+    assert_eq!(decode_escrow(&current.data).unwrap(), f.escrow_state(SEED));
+}
+
+#[test]
+fn test_truncated_escrow_fails_to_decode() {
+    let f = EscrowFixture::new();
+    let data = legacy_bytes(&v1_escrow(&f));
+    for len in [0, LEGACY_DISCRIMINATOR.len(), data.len() - 1] {
+        assert!(decode_escrow(&data[..len]).is_err());
+    }
+    assert!(decode_escrow(Escrow::DISCRIMINATOR).is_err());
+}
+
+#[test]
+fn test_only_legacy_escrows_migrate() {
+    let mut f = EscrowFixture::new();
+    write_v1_escrow(&mut f);
+    migrate(&mut f).unwrap();
+    assert_escrow_error(migrate(&mut f), EscrowError::EscrowAlreadyMigrated);
+
+    // A layout from a future version is left alone
+    let mut data = f.ctx.svm.get_account(&f.escrow(SEED)).unwrap().data;
+    data[Escrow::DISCRIMINATOR.len()] = ESCROW_VERSION + 1;
+    write_escrow_account(&mut f, data);
+    assert_escrow_error(migrate(&mut f), EscrowError::UnsupportedEscrowVersion);
+}
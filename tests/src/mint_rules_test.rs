@@ -0,0 +1,155 @@
+use crate::common::{assert_escrow_error, mint_rule_address, EscrowFixture, SEED};
+use anchor_escrow::{
+    errors::EscrowError,
+    state::{MintListing, MintRule},
+};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use litesvm::types::TransactionResult;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+fn set_mint_rule(
+    f: &mut EscrowFixture,
+    admin: &Keypair,
+    mint: &Pubkey,
+    listing: MintListing,
+) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetMintRule {
+            admin: admin.pubkey(),
+            config: f.config(),
+            mint: *mint,
+            mint_rule: mint_rule_address(mint),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetMintRule { listing }.data(),
+    };
+    f.send(&[ix], &[admin])
+}
+
+fn remove_mint_rule(f: &mut EscrowFixture, admin: &Keypair, mint: &Pubkey) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::RemoveMintRule {
+            admin: admin.pubkey(),
+            config: f.config(),
+            mint_rule: mint_rule_address(mint),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::RemoveMintRule {}.data(),
+    };
+    f.send(&[ix], &[admin])
+}
+
+fn set_allowlist_only(f: &mut EscrowFixture, enabled: bool) -> TransactionResult {
+    let admin = f.admin.insecure_clone();
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetAllowlistOnly {
+            admin: admin.pubkey(),
+            config: f.config(),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetAllowlistOnly { enabled }.data(),
+    };
+    f.send(&[ix], &[&admin])
+}
+
+// Lists `mint` as the fixture's admin
+fn list(f: &mut EscrowFixture, mint: &Pubkey, listing: MintListing) {
+    let admin = f.admin.insecure_clone();
+    set_mint_rule(f, &admin, mint, listing).unwrap();
+}
+
+#[test]
+fn test_denied_mint_a_blocks_make() {
+    let mut f = EscrowFixture::new();
+    let mint_a = f.mint_a.pubkey();
+    list(&mut f, &mint_a, MintListing::Denied);
+
+    assert_escrow_error(f.make(SEED), EscrowError::MintDenied);
+}
+
+#[test]
+fn test_denied_mint_b_blocks_make() {
+    let mut f = EscrowFixture::new();
+    let mint_b = f.mint_b.pubkey();
+    list(&mut f, &mint_b, MintListing::Denied);
+
+    assert_escrow_error(f.make(SEED), EscrowError::MintDenied);
+}
+
+#[test]
+fn test_allowlist_only_needs_both_mints_allowed() {
+    let mut f = EscrowFixture::new();
+    set_allowlist_only(&mut f, true).unwrap();
+    assert_escrow_error(f.make(SEED), EscrowError::MintNotAllowed);
+
+    let mint_a = f.mint_a.pubkey();
+    list(&mut f, &mint_a, MintListing::Allowed);
+    assert_escrow_error(f.make(SEED), EscrowError::MintNotAllowed);
+
+    let mint_b = f.mint_b.pubkey();
+    list(&mut f, &mint_b, MintListing::Allowed);
+    f.make(SEED).unwrap();
+}
+
+#[test]
+fn test_deny_rule_applies_in_allowlist_mode() {
+    let mut f = EscrowFixture::new();
+    set_allowlist_only(&mut f, true).unwrap();
+    let (mint_a, mint_b) = (f.mint_a.pubkey(), f.mint_b.pubkey());
+    list(&mut f, &mint_a, MintListing::Allowed);
+    list(&mut f, &mint_b, MintListing::Denied);
+
+    assert_escrow_error(f.make(SEED), EscrowError::MintDenied);
+}
+
+#[test]
+fn test_relisting_moves_a_mint_between_lists() {
+    let mut f = EscrowFixture::new();
+    let mint_a = f.mint_a.pubkey();
+    list(&mut f, &mint_a, MintListing::Denied);
+    list(&mut f, &mint_a, MintListing::Allowed);
+
+    let account = f.ctx.svm.get_account(&mint_rule_address(&mint_a)).unwrap();
+    let rule = MintRule::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(rule.mint, mint_a);
+    assert_eq!(rule.listing, MintListing::Allowed);
+    f.make(SEED).unwrap();
+}
+
+#[test]
+fn test_removing_a_rule_unlists_the_mint() {
+    let mut f = EscrowFixture::new();
+    let mint_a = f.mint_a.pubkey();
+    list(&mut f, &mint_a, MintListing::Denied);
+    let admin = f.admin.insecure_clone();
+    remove_mint_rule(&mut f, &admin, &mint_a).unwrap();
+
+    assert!(f.is_closed(&mint_rule_address(&mint_a)));
+    f.make(SEED).unwrap();
+}
+
+#[test]
+fn test_only_admin_manages_mint_rules() {
+    let mut f = EscrowFixture::new();
+    let taker = f.taker.insecure_clone();
+    let mint_a = f.mint_a.pubkey();
+
+    assert_escrow_error(
+        set_mint_rule(&mut f, &taker, &mint_a, MintListing::Denied),
+        EscrowError::NotAdmin,
+    );
+
+    list(&mut f, &mint_a, MintListing::Denied);
+    assert_escrow_error(
+        remove_mint_rule(&mut f, &taker, &mint_a),
+        EscrowError::NotAdmin,
+    );
+}
@@ -0,0 +1,67 @@
+use anchor_escrow::oracle::{
+    ACCOUNT_TYPE_PRICE, MAGIC, PRICE_ACCOUNT_LEN, STATUS_TRADING, VERSION,
+};
+use litesvm::LiteSVM;
+use solana_sdk::{account::Account, clock::Clock, pubkey::Pubkey};
+
+// Writes Pyth v2 style price accounts straight into LiteSVM, using the layout
+// documented in `anchor_escrow::oracle`.
+
+pub struct MockPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub timestamp: i64,
+}
+
+impl MockPrice {
+    // A tight, fresh quote at the current clock
+    pub fn new(svm: &LiteSVM, price: i64, expo: i32) -> Self {
+        Self {
+            price,
+            conf: 0,
+            expo,
+            timestamp: svm.get_sysvar::<Clock>().unix_timestamp,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![0u8; PRICE_ACCOUNT_LEN];
+        data[0..4].copy_from_slice(&MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&VERSION.to_le_bytes());
+        data[8..12].copy_from_slice(&ACCOUNT_TYPE_PRICE.to_le_bytes());
+        data[12..16].copy_from_slice(&(PRICE_ACCOUNT_LEN as u32).to_le_bytes());
+        data[20..24].copy_from_slice(&self.expo.to_le_bytes());
+        data[96..104].copy_from_slice(&self.timestamp.to_le_bytes());
+        data[208..216].copy_from_slice(&self.price.to_le_bytes());
+        data[216..224].copy_from_slice(&self.conf.to_le_bytes());
+        data[224..228].copy_from_slice(&STATUS_TRADING.to_le_bytes());
+        data
+    }
+}
+
+// Stand-in for the oracle program, which take only trusts once the admin has
+// configured it
+pub const ORACLE_PROGRAM: Pubkey = Pubkey::new_from_array([7u8; 32]);
+
+pub fn write_price(svm: &mut LiteSVM, feed: Pubkey, price: &MockPrice) {
+    write_price_owned_by(svm, feed, price, ORACLE_PROGRAM);
+}
+
+pub fn write_price_owned_by(svm: &mut LiteSVM, feed: Pubkey, price: &MockPrice, owner: Pubkey) {
+    let data = price.to_bytes();
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(feed, account).unwrap();
+}
+
+pub fn set_unix_timestamp(svm: &mut LiteSVM, unix_timestamp: i64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = unix_timestamp;
+    svm.set_sysvar(&clock);
+}
@@ -0,0 +1,331 @@
+use crate::common::{
+    assert_escrow_error, mint_rule_address, pair_stats_address, EscrowFixture, AMOUNT, RECEIVE,
+    SEED,
+};
+use anchor_escrow::{errors::EscrowError, state::Escrow};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_litesvm::TestHelpers;
+use litesvm::types::TransactionResult;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const MULTISIG_SEED: u64 = 1;
+
+struct Treasury {
+    approvers: Vec<Keypair>,
+    multisig: Pubkey,
+    signer: Pubkey,
+}
+
+impl Treasury {
+    fn escrow(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"escrow", self.signer.as_ref(), &SEED.to_le_bytes()],
+            &anchor_escrow::ID,
+        )
+        .0
+    }
+
+    fn proposal(&self, index: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"proposal", self.multisig.as_ref(), &index.to_le_bytes()],
+            &anchor_escrow::ID,
+        )
+        .0
+    }
+}
+
+fn create_multisig_ix(creator: &Pubkey, approvers: Vec<Pubkey>, threshold: u8) -> Instruction {
+    let multisig = Pubkey::find_program_address(
+        &[b"multisig", creator.as_ref(), &MULTISIG_SEED.to_le_bytes()],
+        &anchor_escrow::ID,
+    )
+    .0;
+    let signer =
+        Pubkey::find_program_address(&[b"multisig_signer", multisig.as_ref()], &anchor_escrow::ID)
+            .0;
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::CreateMultisig {
+            creator: *creator,
+            multisig,
+            signer,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::CreateMultisig {
+            seed: MULTISIG_SEED,
+            approvers,
+            threshold,
+        }
+        .data(),
+    }
+}
+
+// A 2-of-3 multisig whose signer PDA holds SOL for rent and AMOUNT of mint_a
+fn treasury(f: &mut EscrowFixture) -> Treasury {
+    let approvers: Vec<Keypair> = (0..3)
+        .map(|_| f.ctx.create_funded_account(1_000_000_000).unwrap())
+        .collect();
+    let keys = approvers.iter().map(|a| a.pubkey()).collect();
+    let ix = create_multisig_ix(&approvers[0].pubkey(), keys, 2);
+    f.send(&[ix], &[&approvers[0]]).unwrap();
+
+    let multisig = Pubkey::find_program_address(
+        &[
+            b"multisig",
+            approvers[0].pubkey().as_ref(),
+            &MULTISIG_SEED.to_le_bytes(),
+        ],
+        &anchor_escrow::ID,
+    )
+    .0;
+    let signer =
+        Pubkey::find_program_address(&[b"multisig_signer", multisig.as_ref()], &anchor_escrow::ID)
+            .0;
+
+    f.ctx.svm.airdrop(&signer, 1_000_000_000).unwrap();
+    f.init_registry(&signer).unwrap();
+    let mint_a = f.mint_a.pubkey();
+    let signer_ata_a = get_associated_token_address(&signer, &mint_a);
+    let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+        &f.maker.pubkey(),
+        &signer,
+        &mint_a,
+        &spl_token::id(),
+    );
+    let mint_ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &mint_a,
+        &signer_ata_a,
+        &f.maker.pubkey(),
+        &[],
+        AMOUNT,
+    )
+    .unwrap();
+    let maker = f.maker.insecure_clone();
+    f.send(&[create_ata_ix, mint_ix], &[&maker]).unwrap();
+
+    Treasury {
+        approvers,
+        multisig,
+        signer,
+    }
+}
+
+fn propose_make(f: &mut EscrowFixture, t: &Treasury, proposer: &Keypair) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::ProposeMake {
+            proposer: proposer.pubkey(),
+            multisig: t.multisig,
+            proposal: t.proposal(0),
+            mint_a: f.mint_a.pubkey(),
+            mint_b: f.mint_b.pubkey(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::ProposeMake {
+            seed: SEED,
+            receive: RECEIVE,
+            amount: AMOUNT,
+        }
+        .data(),
+    };
+    f.send(&[ix], &[proposer])
+}
+
+fn propose_refund(
+    f: &mut EscrowFixture,
+    t: &Treasury,
+    proposer: &Keypair,
+    index: u64,
+) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::ProposeRefund {
+            proposer: proposer.pubkey(),
+            multisig: t.multisig,
+            proposal: t.proposal(index),
+            maker: t.signer,
+            escrow: t.escrow(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::ProposeRefund {}.data(),
+    };
+    f.send(&[ix], &[proposer])
+}
+
+fn approve(
+    f: &mut EscrowFixture,
+    t: &Treasury,
+    approver: &Keypair,
+    index: u64,
+) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::Approve {
+            approver: approver.pubkey(),
+            multisig: t.multisig,
+            proposal: t.proposal(index),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::Approve {}.data(),
+    };
+    f.send(&[ix], &[approver])
+}
+
+fn execute_make(f: &mut EscrowFixture, t: &Treasury, mint_b: Pubkey) -> TransactionResult {
+    let escrow = t.escrow();
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::ExecuteMake {
+            multisig: t.multisig,
+            proposal: t.proposal(0),
+            proposer: t.approvers[0].pubkey(),
+            maker: t.signer,
+            config: f.config(),
+            escrow,
+            registry: f.registry(&t.signer),
+            maker_stats: f.maker_stats(&t.signer),
+            pair_stats: pair_stats_address(&f.mint_a.pubkey(), &mint_b),
+            mint_a: f.mint_a.pubkey(),
+            mint_b,
+            mint_a_rule: mint_rule_address(&f.mint_a.pubkey()),
+            mint_b_rule: mint_rule_address(&mint_b),
+            maker_ata_a: get_associated_token_address(&t.signer, &f.mint_a.pubkey()),
+            vault: get_associated_token_address(&escrow, &f.mint_a.pubkey()),
+            escrow_program: anchor_escrow::ID,
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::ExecuteMake {}.data(),
+    };
+    // Execution is permissionless, so an outsider pays the fee
+    let payer = f.taker.insecure_clone();
+    f.send(&[ix], &[&payer])
+}
+
+fn execute_refund(f: &mut EscrowFixture, t: &Treasury, index: u64) -> TransactionResult {
+    let escrow = t.escrow();
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::ExecuteRefund {
+            multisig: t.multisig,
+            proposal: t.proposal(index),
+            proposer: t.approvers[1].pubkey(),
+            maker: t.signer,
+            escrow,
+            registry: f.registry(&t.signer),
+            maker_stats: f.maker_stats(&t.signer),
+            pair_stats: f.pair_stats(),
+            mint_a: f.mint_a.pubkey(),
+            vault: get_associated_token_address(&escrow, &f.mint_a.pubkey()),
+            maker_ata_a: get_associated_token_address(&t.signer, &f.mint_a.pubkey()),
+            escrow_program: anchor_escrow::ID,
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::ExecuteRefund {}.data(),
+    };
+    let payer = f.taker.insecure_clone();
+    f.send(&[ix], &[&payer])
+}
+
+// Proposes, approves and executes a make of AMOUNT for RECEIVE
+fn multisig_make(f: &mut EscrowFixture, t: &Treasury) {
+    propose_make(f, t, &t.approvers[0]).unwrap();
+    approve(f, t, &t.approvers[2], 0).unwrap();
+    let mint_b = f.mint_b.pubkey();
+    execute_make(f, t, mint_b).unwrap();
+}
+
+#[test]
+fn test_make_executes_once_threshold_is_reached() {
+    let mut f = EscrowFixture::new();
+    let t = treasury(&mut f);
+    let mint_b = f.mint_b.pubkey();
+
+    propose_make(&mut f, &t, &t.approvers[0]).unwrap();
+    assert_escrow_error(
+        execute_make(&mut f, &t, mint_b),
+        EscrowError::ThresholdNotReached,
+    );
+    assert_escrow_error(
+        approve(&mut f, &t, &t.approvers[0], 0),
+        EscrowError::AlreadyApproved,
+    );
+    let outsider = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    assert_escrow_error(
+        approve(&mut f, &t, &outsider, 0),
+        EscrowError::NotAnApprover,
+    );
+
+    approve(&mut f, &t, &t.approvers[1], 0).unwrap();
+    execute_make(&mut f, &t, mint_b).unwrap();
+
+    let account = f.ctx.svm.get_account(&t.escrow()).unwrap();
+    let escrow = Escrow::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(escrow.maker, t.signer);
+    assert_eq!({ escrow.receive }, RECEIVE);
+    let vault = get_associated_token_address(&t.escrow(), &f.mint_a.pubkey());
+    assert_eq!(f.token_balance(&vault), AMOUNT);
+    assert!(f.is_closed(&t.proposal(0)));
+}
+
+#[test]
+fn test_refund_goes_through_the_same_approval_flow() {
+    let mut f = EscrowFixture::new();
+    let t = treasury(&mut f);
+    multisig_make(&mut f, &t);
+
+    propose_refund(&mut f, &t, &t.approvers[1], 1).unwrap();
+    assert_escrow_error(
+        execute_refund(&mut f, &t, 1),
+        EscrowError::ThresholdNotReached,
+    );
+    approve(&mut f, &t, &t.approvers[2], 1).unwrap();
+    execute_refund(&mut f, &t, 1).unwrap();
+
+    assert!(f.is_closed(&t.escrow()));
+    assert!(f.is_closed(&t.proposal(1)));
+    let signer_ata_a = get_associated_token_address(&t.signer, &f.mint_a.pubkey());
+    assert_eq!(f.token_balance(&signer_ata_a), AMOUNT);
+}
+
+#[test]
+fn test_execute_must_match_the_proposal() {
+    let mut f = EscrowFixture::new();
+    let t = treasury(&mut f);
+    propose_make(&mut f, &t, &t.approvers[0]).unwrap();
+    approve(&mut f, &t, &t.approvers[1], 0).unwrap();
+
+    let wrong_mint_b = f.mint_a.pubkey();
+    assert_escrow_error(
+        execute_make(&mut f, &t, wrong_mint_b),
+        EscrowError::ProposalMismatch,
+    );
+}
+
+#[test]
+fn test_create_multisig_rejects_invalid_approver_sets() {
+    let mut f = EscrowFixture::new();
+    let creator = f.maker.insecure_clone();
+    let a = Pubkey::new_unique();
+    let b = Pubkey::new_unique();
+
+    for (approvers, threshold) in [(vec![a, b], 0), (vec![a, b], 3), (vec![a, a], 1)] {
+        let ix = create_multisig_ix(&creator.pubkey(), approvers, threshold);
+        assert_escrow_error(f.send(&[ix], &[&creator]), EscrowError::InvalidApprovers);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::common::{
+    assert_escrow_error, take_instruction, take_instruction_with_limit, EscrowFixture, AMOUNT,
+    RECEIVE, SEED,
+};
+use crate::mock_oracle::{
+    set_unix_timestamp, write_price, write_price_owned_by, MockPrice, ORACLE_PROGRAM,
+};
+use anchor_escrow::TakeCleanup;
+use anchor_escrow::{errors::EscrowError, oracle::OraclePricing};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::types::TransactionResult;
+use solana_sdk::{clock::Clock, instruction::Instruction, pubkey::Pubkey, signature::Signer};
+
+const FLOOR: u64 = RECEIVE / 2;
+const MAX_STALENESS: u64 = 60;
+
+fn pricing(feed: Pubkey) -> OraclePricing {
+    OraclePricing {
+        feed,
+        spread_bps: 100,
+        max_staleness: MAX_STALENESS,
+        max_confidence_bps: 50,
+    }
+}
+
+fn set_oracle_pricing_ix(f: &EscrowFixture, pricing: Option<OraclePricing>) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetOraclePricing {
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetOraclePricing { pricing }.data(),
+    }
+}
+
+fn set_oracle_program(f: &mut EscrowFixture, oracle_program: Pubkey) {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetOracleProgram {
+            admin: f.admin.pubkey(),
+            config: f.config(),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetOracleProgram { oracle_program }.data(),
+    };
+    let admin = f.admin.insecure_clone();
+    f.send(&[ix], &[&admin]).unwrap();
+}
+
+fn make_oracle_priced(f: &mut EscrowFixture, feed: Pubkey) {
+    set_oracle_program(f, ORACLE_PROGRAM);
+    let make_ix = f.make_ix(SEED, FLOOR, AMOUNT);
+    let set_ix = set_oracle_pricing_ix(f, Some(pricing(feed)));
+    let maker = f.maker.insecure_clone();
+    f.send(&[make_ix, set_ix], &[&maker]).unwrap();
+}
+
+fn take_with_feed(f: &mut EscrowFixture, feed: Option<Pubkey>) -> TransactionResult {
+    let mut accounts = f.take_accounts(SEED, &f.taker.pubkey(), &f.maker.pubkey());
+    accounts.price_feed = feed;
+    let ix = take_instruction(accounts, vec![]);
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+fn maker_ata_b(f: &EscrowFixture) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(
+        &f.maker.pubkey(),
+        &f.mint_b.pubkey(),
+    )
+}
+
+#[test]
+fn test_take_pays_oracle_price_plus_spread() {
+    let mut f = EscrowFixture::new();
+    let feed = Pubkey::new_unique();
+    make_oracle_priced(&mut f, feed);
+    assert_eq!(f.escrow_state(SEED).oracle(), Some(pricing(feed)));
+
+    // 1 mint_a = 0.45 mint_b, plus the 1% spread
+    let price = MockPrice::new(&f.ctx.svm, 45_000_000, -8);
+    write_price(&mut f.ctx.svm, feed, &price);
+    take_with_feed(&mut f, Some(feed)).unwrap();
+
+    assert_eq!(f.token_balance(&maker_ata_b(&f)), 454_500_000);
+    assert_eq!(f.token_balance(&f.taker_ata_b), RECEIVE - 454_500_000);
+}
+
+#[test]
+fn test_receive_is_a_floor_under_the_oracle_price() {
+    let mut f = EscrowFixture::new();
+    let feed = Pubkey::new_unique();
+    make_oracle_priced(&mut f, feed);
+
+    let price = MockPrice::new(&f.ctx.svm, 10_000_000, -8);
+    write_price(&mut f.ctx.svm, feed, &price);
+    take_with_feed(&mut f, Some(feed)).unwrap();
+
+    assert_eq!(f.token_balance(&maker_ata_b(&f)), FLOOR);
+}
+
+#[test]
+fn test_stale_price_is_rejected() {
+    let mut f = EscrowFixture::new();
+    let feed = Pubkey::new_unique();
+    make_oracle_priced(&mut f, feed);
+
+    let price = MockPrice::new(&f.ctx.svm, 45_000_000, -8);
+    write_price(&mut f.ctx.svm, feed, &price);
+
+    set_unix_timestamp(&mut f.ctx.svm, price.timestamp + MAX_STALENESS as i64 + 1);
+    assert_escrow_error(take_with_feed(&mut f, Some(feed)), EscrowError::StalePrice);
+
+    set_unix_timestamp(&mut f.ctx.svm, price.timestamp + MAX_STALENESS as i64);
+    take_with_feed(&mut f, Some(feed)).unwrap();
+}
+
+#[test]
+fn test_non_positive_price_is_rejected() {
+    let mut f = EscrowFixture::new();
+    let feed = Pubkey::new_unique();
+    make_oracle_priced(&mut f, feed);
+
+    for value in [-45_000_000, 0] {
+        let price = MockPrice::new(&f.ctx.svm, value, -8);
+        write_price(&mut f.ctx.svm, feed, &price);
+        assert_escrow_error(
+            take_with_feed(&mut f, Some(feed)),
+            EscrowError::NegativePrice,
+        );
+    }
+}
+
+#[test]
+fn test_wide_confidence_is_rejected() {
+    let mut f = EscrowFixture::new();
+    let feed = Pubkey::new_unique();
+    make_oracle_priced(&mut f, feed);
+
+    // 50 bps of 0.45 is 225_000 at expo -8
+    let mut price = MockPrice::new(&f.ctx.svm, 45_000_000, -8);
+    price.conf = 225_001;
+    write_price(&mut f.ctx.svm, feed, &price);
+    assert_escrow_error(
+        take_with_feed(&mut f, Some(feed)),
+        EscrowError::PriceConfidenceTooWide,
+    );
+
+    price.conf = 225_000;
+    write_price(&mut f.ctx.svm, feed, &price);
+    take_with_feed(&mut f, Some(feed)).unwrap();
+}
+
+#[test]
+fn test_feed_must_be_the_pinned_account() {
+    let mut f = EscrowFixture::new();
+    let feed = Pubkey::new_unique();
+    let impostor = Pubkey::new_unique();
+    make_oracle_priced(&mut f, feed);
+
+    let now = f.ctx.svm.get_sysvar::<Clock>().unix_timestamp;
+    let cheap = MockPrice {
+        price: 1,
+        conf: 0,
+        expo: -8,
+        timestamp: now,
+    };
+    write_price(&mut f.ctx.svm, impostor, &cheap);
+
+    assert_escrow_error(
+        take_with_feed(&mut f, Some(impostor)),
+        EscrowError::InvalidPriceFeed,
+    );
+    assert_escrow_error(take_with_feed(&mut f, None), EscrowError::PriceFeedMissing);
+}
+
+#[test]
+fn test_feed_must_belong_to_the_configured_oracle() {
+    let mut f = EscrowFixture::new();
+    let feed = Pubkey::new_unique();
+    make_oracle_priced(&mut f, feed);
+
+    // A Pyth-shaped account the maker wrote under their own program
+    let price = MockPrice::new(&f.ctx.svm, 90_000_000, -8);
+    write_price_owned_by(&mut f.ctx.svm, feed, &price, Pubkey::new_unique());
+    assert_escrow_error(
+        take_with_feed(&mut f, Some(feed)),
+        EscrowError::UntrustedPriceFeed,
+    );
+}
+
+#[test]
+fn test_take_fails_when_price_is_above_max_receive() {
+    let mut f = EscrowFixture::new();
+    let feed = Pubkey::new_unique();
+    make_oracle_priced(&mut f, feed);
+    let price = MockPrice::new(&f.ctx.svm, 45_000_000, -8);
+    write_price(&mut f.ctx.svm, feed, &price);
+
+    let taker = f.taker.insecure_clone();
+    for (max_receive, allowed) in [(454_499_999, false), (454_500_000, true)] {
+        let mut accounts = f.take_accounts(SEED, &taker.pubkey(), &f.maker.pubkey());
+        accounts.price_feed = Some(feed);
+        let ix = take_instruction_with_limit(accounts, vec![], TakeCleanup::default(), max_receive);
+        let result = f.send(&[ix], &[&taker]);
+        if allowed {
+            result.unwrap();
+        } else {
+            assert_escrow_error(result, EscrowError::ReceiveAboveLimit);
+        }
+    }
+    assert_eq!(f.token_balance(&maker_ata_b(&f)), 454_500_000);
+}
@@ -0,0 +1,171 @@
+use crate::common::{
+    assert_escrow_error, deploy_upgradeable, init_config_instruction, EscrowFixture, PROGRAM_BYTES,
+    SEED,
+};
+use anchor_escrow::errors::EscrowError;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_litesvm::{AnchorLiteSVM, TestHelpers};
+use litesvm::types::TransactionResult;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+fn set_paused(f: &mut EscrowFixture, admin: &Keypair, paused: bool) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetPaused {
+            admin: admin.pubkey(),
+            config: f.config(),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetPaused { paused }.data(),
+    };
+    f.send(&[ix], &[admin])
+}
+
+fn propose_admin(
+    f: &mut EscrowFixture,
+    admin: &Keypair,
+    new_admin: Option<Pubkey>,
+) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::ProposeAdmin {
+            admin: admin.pubkey(),
+            config: f.config(),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::ProposeAdmin { new_admin }.data(),
+    };
+    f.send(&[ix], &[admin])
+}
+
+fn accept_admin(f: &mut EscrowFixture, pending_admin: &Keypair) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::AcceptAdmin {
+            pending_admin: pending_admin.pubkey(),
+            config: f.config(),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::AcceptAdmin {}.data(),
+    };
+    f.send(&[ix], &[pending_admin])
+}
+
+#[test]
+fn test_pause_blocks_make() {
+    let mut f = EscrowFixture::new();
+    f.pause();
+
+    assert_escrow_error(f.make(SEED), EscrowError::ProgramPaused);
+}
+
+#[test]
+fn test_pause_blocks_take() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    f.pause();
+
+    assert_escrow_error(f.take(SEED), EscrowError::ProgramPaused);
+}
+
+#[test]
+fn test_refund_works_while_paused() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    f.pause();
+
+    f.refund(SEED).unwrap();
+    assert!(f.is_closed(&f.escrow(SEED)));
+}
+
+#[test]
+fn test_unpause_reopens_make_and_take() {
+    let mut f = EscrowFixture::new();
+    f.pause();
+    let admin = f.admin.insecure_clone();
+    set_paused(&mut f, &admin, false).unwrap();
+
+    f.make(SEED).unwrap();
+    f.take(SEED).unwrap();
+}
+
+#[test]
+fn test_only_admin_can_pause() {
+    let mut f = EscrowFixture::new();
+    let taker = f.taker.insecure_clone();
+
+    assert_escrow_error(set_paused(&mut f, &taker, true), EscrowError::NotAdmin);
+    f.make(SEED).unwrap();
+}
+
+#[test]
+fn test_admin_handover() {
+    let mut f = EscrowFixture::new();
+    let old_admin = f.admin.insecure_clone();
+    let new_admin = f.taker.insecure_clone();
+
+    propose_admin(&mut f, &old_admin, Some(new_admin.pubkey())).unwrap();
+    // Proposing alone hands nothing over
+    assert_escrow_error(set_paused(&mut f, &new_admin, true), EscrowError::NotAdmin);
+
+    accept_admin(&mut f, &new_admin).unwrap();
+    assert_escrow_error(set_paused(&mut f, &old_admin, true), EscrowError::NotAdmin);
+    set_paused(&mut f, &new_admin, true).unwrap();
+    assert_escrow_error(f.make(SEED), EscrowError::ProgramPaused);
+}
+
+#[test]
+fn test_only_pending_admin_can_accept() {
+    let mut f = EscrowFixture::new();
+    let admin = f.admin.insecure_clone();
+    let maker = f.maker.insecure_clone();
+    let taker = f.taker.insecure_clone();
+
+    assert_escrow_error(accept_admin(&mut f, &taker), EscrowError::NotPendingAdmin);
+
+    propose_admin(&mut f, &admin, Some(taker.pubkey())).unwrap();
+    assert_escrow_error(accept_admin(&mut f, &maker), EscrowError::NotPendingAdmin);
+
+    // Withdrawing the offer shuts out the former candidate too
+    propose_admin(&mut f, &admin, None).unwrap();
+    assert_escrow_error(accept_admin(&mut f, &taker), EscrowError::NotPendingAdmin);
+}
+
+#[test]
+fn test_only_admin_can_propose() {
+    let mut f = EscrowFixture::new();
+    let taker = f.taker.insecure_clone();
+
+    assert_escrow_error(
+        propose_admin(&mut f, &taker, Some(taker.pubkey())),
+        EscrowError::NotAdmin,
+    );
+}
+
+#[test]
+fn test_only_upgrade_authority_can_init_config() {
+    let mut ctx = AnchorLiteSVM::build_with_program(anchor_escrow::ID, PROGRAM_BYTES);
+    let authority = ctx.create_funded_account(1_000_000_000).unwrap();
+    let stranger = ctx.create_funded_account(1_000_000_000).unwrap();
+    deploy_upgradeable(&mut ctx.svm, &authority.pubkey());
+
+    for (admin, allowed) in [(&stranger, false), (&authority, true)] {
+        let tx = Transaction::new_signed_with_payer(
+            &[init_config_instruction(&admin.pubkey())],
+            Some(&admin.pubkey()),
+            &[admin],
+            ctx.svm.latest_blockhash(),
+        );
+        let result = ctx.svm.send_transaction(tx);
+        if allowed {
+            result.unwrap();
+        } else {
+            assert_escrow_error(result, EscrowError::NotUpgradeAuthority);
+        }
+    }
+}
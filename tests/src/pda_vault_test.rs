@@ -0,0 +1,72 @@
+use crate::common::{with_vault, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use anchor_escrow::state::VaultKind;
+use solana_sdk::signature::Signer;
+use spl_associated_token_account::get_associated_token_address;
+
+#[test]
+fn test_make_with_pda_vault() {
+    let mut f = EscrowFixture::new();
+    f.make_with_pda_vault(SEED).unwrap();
+
+    let escrow = f.escrow_state(SEED);
+    assert_eq!(escrow.vault_kind(), VaultKind::Pda);
+    let vault = escrow.vault(&f.escrow(SEED), &spl_token::id()).unwrap();
+    assert_eq!(vault, f.pda_vault(SEED));
+    assert_eq!(f.token_balance(&f.pda_vault(SEED)), AMOUNT);
+    assert!(f.is_closed(&f.vault(SEED)));
+}
+
+#[test]
+fn test_take_from_pda_vault() {
+    let mut f = EscrowFixture::new();
+    f.make_with_pda_vault(SEED).unwrap();
+    f.take_from_pda_vault(SEED).unwrap();
+
+    let taker_ata_a = get_associated_token_address(&f.taker.pubkey(), &f.mint_a.pubkey());
+    assert_eq!(f.token_balance(&taker_ata_a), AMOUNT);
+    assert!(f.is_closed(&f.pda_vault(SEED)));
+    assert!(f.is_closed(&f.escrow(SEED)));
+}
+
+#[test]
+fn test_refund_from_pda_vault() {
+    let mut f = EscrowFixture::new();
+    f.make_with_pda_vault(SEED).unwrap();
+    f.refund_from_pda_vault(SEED).unwrap();
+
+    assert_eq!(f.token_balance(&f.maker_ata_a), AMOUNT);
+    assert!(f.is_closed(&f.pda_vault(SEED)));
+    assert!(f.is_closed(&f.escrow(SEED)));
+}
+
+#[test]
+fn test_pda_vault_escrow_rejects_ata_vault() {
+    let mut f = EscrowFixture::new();
+    f.make_with_pda_vault(SEED).unwrap();
+
+    // The escrow's ATA address is not its vault, and was never created
+    assert!(f.take(SEED).is_err());
+    assert!(f.refund(SEED).is_err());
+}
+
+#[test]
+fn test_make_with_pda_vault_rejects_other_vault() {
+    let mut f = EscrowFixture::new();
+    let mut ix = f.make_with_pda_vault_ix(SEED, RECEIVE, AMOUNT);
+    with_vault(&mut ix, &f.pda_vault(SEED), &f.vault(SEED));
+    let maker = f.maker.insecure_clone();
+    assert!(f.send(&[ix], &[&maker]).is_err());
+}
+
+#[test]
+fn test_make_with_pda_vault_survives_prefunded_address() {
+    let mut f = EscrowFixture::new();
+    // Lamports sent to the vault address ahead of make can't block it
+    let vault = f.pda_vault(SEED);
+    f.ctx.svm.airdrop(&vault, 1_000).unwrap();
+
+    f.make_with_pda_vault(SEED).unwrap();
+
+    assert_eq!(f.token_balance(&vault), AMOUNT);
+    f.take_from_pda_vault(SEED).unwrap();
+}
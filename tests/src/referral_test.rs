@@ -0,0 +1,209 @@
+use crate::common::{
+    assert_escrow_error, take_instruction_with_limit, EscrowFixture, AMOUNT, RECEIVE, SEED,
+};
+use anchor_escrow::errors::EscrowError;
+use anchor_escrow::TakeCleanup;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::types::TransactionResult;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
+
+fn set_referral_bps(
+    f: &mut EscrowFixture,
+    admin: &Keypair,
+    referral_bps: u16,
+) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetReferralBps {
+            admin: admin.pubkey(),
+            config: f.config(),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetReferralBps { referral_bps }.data(),
+    };
+    f.send(&[ix], &[admin])
+}
+
+// The referrer's mint_b ATA, created up front as front ends are expected to
+fn open_referrer_ata_b(f: &mut EscrowFixture, referrer: &Pubkey) -> Pubkey {
+    let taker = f.taker.insecure_clone();
+    let ix = create_associated_token_account(
+        &taker.pubkey(),
+        referrer,
+        &f.mint_b.pubkey(),
+        &spl_token::id(),
+    );
+    f.send(&[ix], &[&taker]).unwrap();
+    get_associated_token_address(referrer, &f.mint_b.pubkey())
+}
+
+fn take_referred(
+    f: &mut EscrowFixture,
+    referrer: Option<Pubkey>,
+    referrer_ata_b: Option<Pubkey>,
+) -> TransactionResult {
+    take_referred_with_limit(f, referrer, referrer_ata_b, u64::MAX)
+}
+
+fn take_referred_with_limit(
+    f: &mut EscrowFixture,
+    referrer: Option<Pubkey>,
+    referrer_ata_b: Option<Pubkey>,
+    max_receive: u64,
+) -> TransactionResult {
+    let mut accounts = f.take_accounts(SEED, &f.taker.pubkey(), &f.maker.pubkey());
+    accounts.referrer = referrer;
+    accounts.referrer_ata_b = referrer_ata_b;
+    let ix = take_instruction_with_limit(accounts, vec![], TakeCleanup::default(), max_receive);
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+// Mints more mint_b to the taker, who pays the referral fee on top of `receive`
+fn fund_taker(f: &mut EscrowFixture, amount: u64) {
+    let ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &f.mint_b.pubkey(),
+        &f.taker_ata_b,
+        &f.maker.pubkey(),
+        &[],
+        amount,
+    )
+    .unwrap();
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+}
+
+fn setup(referral_bps: u16) -> EscrowFixture {
+    let mut f = EscrowFixture::new();
+    let admin = f.admin.insecure_clone();
+    set_referral_bps(&mut f, &admin, referral_bps).unwrap();
+    f.make(SEED).unwrap();
+    f
+}
+
+fn maker_ata_b(f: &EscrowFixture) -> Pubkey {
+    get_associated_token_address(&f.maker.pubkey(), &f.mint_b.pubkey())
+}
+
+#[test]
+fn test_taker_pays_the_referral_fee_on_top() {
+    // 2.5% of 0.5 tokens
+    let mut f = setup(250);
+    fund_taker(&mut f, 12_500_000);
+    let referrer = Keypair::new().pubkey();
+    let referrer_ata_b = open_referrer_ata_b(&mut f, &referrer);
+
+    take_referred(&mut f, Some(referrer), Some(referrer_ata_b)).unwrap();
+
+    assert_eq!(f.token_balance(&referrer_ata_b), 12_500_000);
+    assert_eq!(f.token_balance(&maker_ata_b(&f)), RECEIVE);
+    assert_eq!(f.token_balance(&f.taker_ata_b), 0);
+}
+
+#[test]
+fn test_referral_fee_rounds_down() {
+    // 1 bp of 0.5 tokens is exact, so shave a unit off the price to check
+    // that the remainder stays with the taker
+    let mut f = EscrowFixture::new();
+    let admin = f.admin.insecure_clone();
+    set_referral_bps(&mut f, &admin, 1).unwrap();
+    let ix = f.make_ix(SEED, RECEIVE - 1, AMOUNT);
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+    fund_taker(&mut f, 50_000);
+    let referrer = Keypair::new().pubkey();
+    let referrer_ata_b = open_referrer_ata_b(&mut f, &referrer);
+
+    take_referred(&mut f, Some(referrer), Some(referrer_ata_b)).unwrap();
+
+    assert_eq!(f.token_balance(&referrer_ata_b), 49_999);
+    assert_eq!(f.token_balance(&maker_ata_b(&f)), RECEIVE - 1);
+    assert_eq!(f.token_balance(&f.taker_ata_b), 2);
+}
+
+#[test]
+fn test_max_receive_covers_the_referral_fee() {
+    let mut f = setup(250);
+    fund_taker(&mut f, 12_500_000);
+    let referrer = Keypair::new().pubkey();
+    let referrer_ata_b = open_referrer_ata_b(&mut f, &referrer);
+
+    assert_escrow_error(
+        take_referred_with_limit(&mut f, Some(referrer), Some(referrer_ata_b), RECEIVE),
+        EscrowError::ReceiveAboveLimit,
+    );
+    take_referred_with_limit(
+        &mut f,
+        Some(referrer),
+        Some(referrer_ata_b),
+        RECEIVE + 12_500_000,
+    )
+    .unwrap();
+    assert_eq!(f.token_balance(&maker_ata_b(&f)), RECEIVE);
+}
+
+#[test]
+fn test_take_without_referrer_pays_the_maker_in_full() {
+    let mut f = setup(250);
+
+    take_referred(&mut f, None, None).unwrap();
+
+    assert_eq!(f.token_balance(&maker_ata_b(&f)), RECEIVE);
+}
+
+#[test]
+fn test_referrer_cannot_be_the_maker() {
+    let mut f = setup(250);
+    let maker = f.maker.pubkey();
+    let maker_ata_b = open_referrer_ata_b(&mut f, &maker);
+
+    assert_escrow_error(
+        take_referred(&mut f, Some(maker), Some(maker_ata_b)),
+        EscrowError::ReferrerIsMaker,
+    );
+}
+
+#[test]
+fn test_referrer_cannot_be_the_taker() {
+    let mut f = setup(250);
+    let taker = f.taker.pubkey();
+    let taker_ata_b = f.taker_ata_b;
+
+    assert_escrow_error(
+        take_referred(&mut f, Some(taker), Some(taker_ata_b)),
+        EscrowError::ReferrerIsTaker,
+    );
+}
+
+#[test]
+fn test_referrer_needs_its_token_account() {
+    let mut f = setup(250);
+    let referrer = Keypair::new().pubkey();
+
+    assert_escrow_error(
+        take_referred(&mut f, Some(referrer), None),
+        EscrowError::ReferralAccountMismatch,
+    );
+}
+
+#[test]
+fn test_only_admin_sets_referral_share() {
+    let mut f = EscrowFixture::new();
+    let taker = f.taker.insecure_clone();
+    let admin = f.admin.insecure_clone();
+
+    assert_escrow_error(set_referral_bps(&mut f, &taker, 250), EscrowError::NotAdmin);
+    assert_escrow_error(
+        set_referral_bps(&mut f, &admin, 1_001),
+        EscrowError::InvalidReferralBps,
+    );
+    set_referral_bps(&mut f, &admin, 1_000).unwrap();
+}
@@ -1,3 +1,4 @@
+use crate::common::{deploy_upgradeable, program_data_address};
 use litesvm::LiteSVM;
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
@@ -34,6 +35,9 @@ fn test_make_and_take_with_regular_litesvm() {
     svm.airdrop(&maker.pubkey(), 10_000_000_000).unwrap();
     svm.airdrop(&taker.pubkey(), 10_000_000_000).unwrap();
 
+    // init_config only accepts the program's upgrade authority as admin
+    deploy_upgradeable(&mut svm, &maker.pubkey());
+
     // Create two token mints
     let mint_a = Keypair::new();
     let mint_b = Keypair::new();
@@ -148,6 +152,63 @@ fn test_make_and_take_with_regular_litesvm() {
 
     let vault = get_associated_token_address(&escrow_pda, &mint_a.pubkey());
 
+    // Make opens the registry on first use, but a maker can open it up front
+    let (registry_pda, _bump) = Pubkey::find_program_address(
+        &[b"registry", maker.pubkey().as_ref()],
+        &program_id,
+    );
+    let (maker_stats_pda, _bump) = Pubkey::find_program_address(
+        &[b"maker_stats", maker.pubkey().as_ref()],
+        &program_id,
+    );
+    let (pair_stats_pda, _bump) = Pubkey::find_program_address(
+        &[b"pair_stats", mint_a.pubkey().as_ref(), mint_b.pubkey().as_ref()],
+        &program_id,
+    );
+    // The program config is created once per deployment, by its admin
+    let (config_pda, _bump) = Pubkey::find_program_address(&[b"config"], &program_id);
+    // Neither mint is listed, which make reads from their empty rule accounts
+    let (mint_a_rule_pda, _bump) =
+        Pubkey::find_program_address(&[b"mint_rule", mint_a.pubkey().as_ref()], &program_id);
+    let (mint_b_rule_pda, _bump) =
+        Pubkey::find_program_address(&[b"mint_rule", mint_b.pubkey().as_ref()], &program_id);
+
+    let mut hasher = Sha256::new();
+    hasher.update(b"global:init_config");
+    let hash = hasher.finalize();
+    let mut init_config_discriminator = [0u8; 8];
+    init_config_discriminator.copy_from_slice(&hash[..8]);
+
+    let init_config_instruction = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(maker.pubkey(), true), // admin
+            AccountMeta::new_readonly(program_id, false), // program
+            AccountMeta::new_readonly(program_data_address(), false), // program_data
+            AccountMeta::new(config_pda, false),    // config
+            AccountMeta::new_readonly(system_program::id(), false), // system_program
+        ],
+        data: init_config_discriminator.to_vec(),
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(b"global:init_registry");
+    let hash = hasher.finalize();
+    let mut init_registry_discriminator = [0u8; 8];
+    init_registry_discriminator.copy_from_slice(&hash[..8]);
+
+    let init_registry_instruction = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(maker.pubkey(), true),   // payer
+            AccountMeta::new_readonly(maker.pubkey(), false), // maker
+            AccountMeta::new(registry_pda, false),    // registry
+            AccountMeta::new(maker_stats_pda, false), // maker_stats
+            AccountMeta::new_readonly(system_program::id(), false), // system_program
+        ],
+        data: init_registry_discriminator.to_vec(),
+    };
+
     // Build make instruction discriminator
     let mut hasher = Sha256::new();
     hasher.update(b"global:make");
@@ -171,10 +232,17 @@ fn test_make_and_take_with_regular_litesvm() {
     let make_instruction = Instruction {
         program_id,
         accounts: vec![
-            AccountMeta::new(maker.pubkey(), true),  // maker
+            AccountMeta::new(maker.pubkey(), true),  // payer
+            AccountMeta::new_readonly(maker.pubkey(), true), // maker
+            AccountMeta::new_readonly(config_pda, false), // config
             AccountMeta::new(escrow_pda, false),      // escrow
+            AccountMeta::new(registry_pda, false),    // registry
+            AccountMeta::new(maker_stats_pda, false), // maker_stats
+            AccountMeta::new(pair_stats_pda, false),  // pair_stats
             AccountMeta::new_readonly(mint_a.pubkey(), false), // mint_a
             AccountMeta::new_readonly(mint_b.pubkey(), false), // mint_b
+            AccountMeta::new_readonly(mint_a_rule_pda, false), // mint_a_rule
+            AccountMeta::new_readonly(mint_b_rule_pda, false), // mint_b_rule
             AccountMeta::new(maker_ata_a, false),     // maker_ata_a
             AccountMeta::new(vault, false),           // vault
             AccountMeta::new_readonly(spl_associated_token_account::id(), false), // associated_token_program
@@ -186,7 +254,11 @@ fn test_make_and_take_with_regular_litesvm() {
 
     // Send make transaction
     let tx = Transaction::new_signed_with_payer(
-        &[make_instruction],
+        &[
+            init_config_instruction,
+            init_registry_instruction,
+            make_instruction,
+        ],
         Some(&maker.pubkey()),
         &[&maker],
         svm.latest_blockhash(),
@@ -206,16 +278,25 @@ fn test_make_and_take_with_regular_litesvm() {
     let mut take_discriminator = [0u8; 8];
     take_discriminator.copy_from_slice(&hash[..8]);
 
-    // Take instruction has no arguments, just the discriminator
-    let take_instruction_data = take_discriminator.to_vec();
+    // Take's arguments are the allowlist proof, an empty Vec<[u8; 32]> here,
+    // the cleanup flags, both off, and the most the taker will pay
+    let mut take_instruction_data = take_discriminator.to_vec();
+    take_instruction_data.extend_from_slice(&0u32.to_le_bytes());
+    take_instruction_data.extend_from_slice(&[0, 0]);
+    take_instruction_data.extend_from_slice(&500_000_000u64.to_le_bytes());
 
     // Build the take instruction with all required accounts
     let take_instruction = Instruction {
         program_id,
         accounts: vec![
+            AccountMeta::new(taker.pubkey(), true),   // payer
             AccountMeta::new(taker.pubkey(), true),   // taker
             AccountMeta::new(maker.pubkey(), false),  // maker
+            AccountMeta::new_readonly(config_pda, false), // config
             AccountMeta::new(escrow_pda, false),      // escrow
+            AccountMeta::new(registry_pda, false),    // registry
+            AccountMeta::new(maker_stats_pda, false), // maker_stats
+            AccountMeta::new(pair_stats_pda, false),  // pair_stats
             AccountMeta::new_readonly(mint_a.pubkey(), false), // mint_a
             AccountMeta::new_readonly(mint_b.pubkey(), false), // mint_b
             AccountMeta::new(vault, false),           // vault
@@ -225,6 +306,14 @@ fn test_make_and_take_with_regular_litesvm() {
             AccountMeta::new_readonly(spl_associated_token_account::id(), false), // associated_token_program
             AccountMeta::new_readonly(spl_token::id(), false), // token_program
             AccountMeta::new_readonly(system_program::id(), false), // system_program
+            AccountMeta::new_readonly(program_id, false), // taker_gate_account (None)
+            AccountMeta::new_readonly(program_id, false), // price_feed (None)
+            AccountMeta::new_readonly(program_id, false), // vesting (None)
+            AccountMeta::new_readonly(program_id, false), // vesting_vault (None)
+            AccountMeta::new_readonly(program_id, false), // reservation (None)
+            AccountMeta::new_readonly(program_id, false), // instructions_sysvar (None)
+            AccountMeta::new_readonly(program_id, false), // referrer (None)
+            AccountMeta::new_readonly(program_id, false), // referrer_ata_b (None)
         ],
         data: take_instruction_data,
     };
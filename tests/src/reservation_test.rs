@@ -0,0 +1,254 @@
+use crate::common::{assert_escrow_error, take_instruction, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use anchor_escrow::{
+    errors::EscrowError,
+    state::{Reservation, ReservationTerms},
+};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_litesvm::TestHelpers;
+use litesvm::types::TransactionResult;
+use solana_sdk::{
+    clock::Clock,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const TERMS: ReservationTerms = ReservationTerms {
+    bond: 100_000_000,
+    window: 50,
+};
+
+fn reservation(f: &EscrowFixture) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"reservation", f.escrow(SEED).as_ref()],
+        &anchor_escrow::ID,
+    )
+    .0
+}
+
+fn reservation_state(f: &EscrowFixture) -> Reservation {
+    let account = f.ctx.svm.get_account(&reservation(f)).unwrap();
+    Reservation::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn lamports(f: &EscrowFixture, address: &Pubkey) -> u64 {
+    f.ctx.svm.get_account(address).map_or(0, |a| a.lamports)
+}
+
+fn set_terms_ix(f: &EscrowFixture, terms: Option<ReservationTerms>) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetReservationTerms {
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetReservationTerms { terms }.data(),
+    }
+}
+
+fn make_reservable(f: &mut EscrowFixture, terms: Option<ReservationTerms>) {
+    let make_ix = f.make_ix(SEED, RECEIVE, AMOUNT);
+    let set_ix = set_terms_ix(f, terms);
+    let maker = f.maker.insecure_clone();
+    f.send(&[make_ix, set_ix], &[&maker]).unwrap();
+}
+
+fn reserve(f: &mut EscrowFixture) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::Reserve {
+            taker: f.taker.pubkey(),
+            escrow: f.escrow(SEED),
+            reservation: reservation(f),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::Reserve {}.data(),
+    };
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+fn forfeit(f: &mut EscrowFixture) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::ForfeitReservation {
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+            reservation: reservation(f),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::ForfeitReservation {}.data(),
+    };
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker])
+}
+
+fn take_as(f: &mut EscrowFixture, taker: &Keypair, with_reservation: bool) -> TransactionResult {
+    let mut accounts = f.take_accounts(SEED, &taker.pubkey(), &f.maker.pubkey());
+    if with_reservation {
+        accounts.reservation = Some(reservation(f));
+    }
+    let ix = take_instruction(accounts, vec![]);
+    f.send(&[ix], &[taker])
+}
+
+// A second taker with enough mint_b to fill the escrow
+fn sniper(f: &mut EscrowFixture) -> Keypair {
+    let sniper = f.ctx.create_funded_account(10_000_000_000).unwrap();
+    f.ctx
+        .create_token_account(&sniper, &f.mint_b.pubkey(), Some((RECEIVE, &f.maker)))
+        .unwrap();
+    sniper
+}
+
+fn warp_past_window(f: &mut EscrowFixture) {
+    let expires_slot = reservation_state(f).expires_slot;
+    f.ctx.svm.warp_to_slot(expires_slot + 1);
+}
+
+#[test]
+fn test_reservation_is_exclusive_and_bond_returns_on_take() {
+    let mut f = EscrowFixture::new();
+    make_reservable(&mut f, Some(TERMS));
+    reserve(&mut f).unwrap();
+
+    let slot = f.ctx.svm.get_sysvar::<Clock>().slot;
+    let state = reservation_state(&f);
+    assert_eq!(state.taker, f.taker.pubkey());
+    assert_eq!(state.bond, TERMS.bond);
+    assert_eq!(state.expires_slot, slot + TERMS.window);
+    assert!(f.escrow_state(SEED).reserved());
+
+    let sniper = sniper(&mut f);
+    assert_escrow_error(
+        take_as(&mut f, &sniper, false),
+        EscrowError::ReservationMissing,
+    );
+    f.ctx.svm.warp_to_slot(state.expires_slot);
+    assert_escrow_error(take_as(&mut f, &sniper, true), EscrowError::EscrowReserved);
+
+    let taker = f.taker.insecure_clone();
+    let reservation_lamports = lamports(&f, &reservation(&f));
+    let taker_before = lamports(&f, &taker.pubkey());
+    take_as(&mut f, &taker, true).unwrap();
+
+    assert!(f.is_closed(&reservation(&f)));
+    assert!(f.is_closed(&f.escrow(SEED)));
+    // Less the fee and the taker_ata_a and maker_ata_b rent the taker pays
+    let ata_rent = f.ctx.svm.minimum_balance_for_rent_exemption(165);
+    assert_eq!(
+        lamports(&f, &taker.pubkey()),
+        taker_before + reservation_lamports - 2 * ata_rent - 5_000
+    );
+}
+
+#[test]
+fn test_lapsed_reservation_bond_goes_to_maker_on_take() {
+    let mut f = EscrowFixture::new();
+    make_reservable(&mut f, Some(TERMS));
+    reserve(&mut f).unwrap();
+    warp_past_window(&mut f);
+
+    let registry = f.registry(&f.maker.pubkey());
+    let released = lamports(&f, &reservation(&f))
+        + lamports(&f, &f.escrow(SEED))
+        + lamports(&f, &f.vault(SEED));
+    let registry_before = lamports(&f, &registry);
+    let maker_before = lamports(&f, &f.maker.pubkey());
+
+    let sniper = sniper(&mut f);
+    take_as(&mut f, &sniper, true).unwrap();
+
+    assert!(f.is_closed(&reservation(&f)));
+    assert_eq!(
+        lamports(&f, &f.maker.pubkey()),
+        maker_before + released + registry_before - lamports(&f, &registry)
+    );
+}
+
+#[test]
+fn test_forfeit_reopens_escrow_after_window() {
+    let mut f = EscrowFixture::new();
+    make_reservable(&mut f, Some(TERMS));
+    reserve(&mut f).unwrap();
+
+    assert_escrow_error(f.refund(SEED), EscrowError::EscrowReserved);
+    assert_escrow_error(forfeit(&mut f), EscrowError::ReservationActive);
+
+    warp_past_window(&mut f);
+    forfeit(&mut f).unwrap();
+
+    assert!(f.is_closed(&reservation(&f)));
+    assert!(!f.escrow_state(SEED).reserved());
+    f.refund(SEED).unwrap();
+}
+
+#[test]
+fn test_reserve_requires_maker_terms() {
+    let mut f = EscrowFixture::new();
+    make_reservable(&mut f, None);
+
+    assert_escrow_error(reserve(&mut f), EscrowError::ReservationsDisabled);
+}
+
+#[test]
+fn test_reservation_terms_need_a_bond_and_window() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    let maker = f.maker.insecure_clone();
+
+    for terms in [
+        ReservationTerms { bond: 0, ..TERMS },
+        ReservationTerms { window: 0, ..TERMS },
+    ] {
+        let ix = set_terms_ix(&f, Some(terms));
+        assert_escrow_error(f.send(&[ix], &[&maker]), EscrowError::InvalidAmount);
+    }
+}
+
+#[test]
+fn test_maker_cannot_change_terms_while_reserved() {
+    let mut f = EscrowFixture::new();
+    make_reservable(&mut f, Some(TERMS));
+    reserve(&mut f).unwrap();
+    let maker = f.maker.insecure_clone();
+
+    // An allowlist leaving out the reserved taker would let the maker keep
+    // their bond once the window lapsed
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetAllowlist {
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetAllowlist {
+            root: Some([1; 32]),
+        }
+        .data(),
+    };
+    assert_escrow_error(f.send(&[ix], &[&maker]), EscrowError::EscrowReserved);
+
+    let new_maker = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::TransferEscrow {
+            maker: f.maker.pubkey(),
+            new_maker: new_maker.pubkey(),
+            escrow: f.escrow(SEED),
+            registry: f.registry(&f.maker.pubkey()),
+            new_registry: f.registry(&new_maker.pubkey()),
+            new_maker_stats: f.maker_stats(&new_maker.pubkey()),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::TransferEscrow {}.data(),
+    };
+    assert_escrow_error(f.send(&[ix], &[&maker]), EscrowError::EscrowReserved);
+
+    // The reserved taker still fills on the terms they reserved
+    let taker = f.taker.insecure_clone();
+    take_as(&mut f, &taker, true).unwrap();
+}
@@ -0,0 +1,408 @@
+use crate::common::{
+    assert_escrow_error, counts, mint_rule_address, EscrowFixture, AMOUNT, RECEIVE, SEED,
+};
+use anchor_escrow::{
+    errors::EscrowError,
+    state::{MakerRegistry, ReservationTerms, Rfq, RfqStatus},
+    vesting::VestingSchedule,
+};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_litesvm::TestHelpers;
+use litesvm::types::TransactionResult;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const RFQ_SEED: u64 = 7;
+
+fn rfq(f: &EscrowFixture) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"rfq", f.taker.pubkey().as_ref(), &RFQ_SEED.to_le_bytes()],
+        &anchor_escrow::ID,
+    )
+    .0
+}
+
+fn rfq_state(f: &EscrowFixture) -> Rfq {
+    let account = f.ctx.svm.get_account(&rfq(f)).unwrap();
+    Rfq::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn registry_state(f: &EscrowFixture, maker: &Pubkey) -> MakerRegistry {
+    let account = f.ctx.svm.get_account(&f.registry(maker)).unwrap();
+    assert_eq!(account.data.len(), MakerRegistry::space(0));
+    MakerRegistry::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn escrow_of(maker: &Pubkey, seed: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"escrow", maker.as_ref(), &seed.to_le_bytes()],
+        &anchor_escrow::ID,
+    )
+    .0
+}
+
+fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(owner, mint)
+}
+
+fn open_rfq(f: &mut EscrowFixture, amount: u64) {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::OpenRfq {
+            taker: f.taker.pubkey(),
+            rfq: rfq(f),
+            mint_a: f.mint_a.pubkey(),
+            mint_b: f.mint_b.pubkey(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::OpenRfq {
+            seed: RFQ_SEED,
+            amount,
+        }
+        .data(),
+    };
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker]).unwrap();
+}
+
+// A second maker holding AMOUNT of mint_a, with a registry
+fn new_maker(f: &mut EscrowFixture) -> Keypair {
+    let maker = f.ctx.create_funded_account(10_000_000_000).unwrap();
+    f.ctx
+        .create_token_account(&maker, &f.mint_a.pubkey(), Some((AMOUNT, &f.maker)))
+        .unwrap();
+    f.init_registry(&maker.pubkey()).unwrap();
+    maker
+}
+
+fn submit_quote_ix(f: &EscrowFixture, maker: &Pubkey, seed: u64) -> Instruction {
+    let escrow = escrow_of(maker, seed);
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SubmitQuote {
+            maker: *maker,
+            config: f.config(),
+            escrow,
+            vault: ata(&escrow, &f.mint_a.pubkey()),
+            rfq: rfq(f),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SubmitQuote {}.data(),
+    }
+}
+
+// Makes an escrow of `amount` for `receive` and quotes it into the RFQ
+fn quote(
+    f: &mut EscrowFixture,
+    maker: &Keypair,
+    seed: u64,
+    receive: u64,
+    amount: u64,
+) -> TransactionResult {
+    let escrow = escrow_of(&maker.pubkey(), seed);
+    let make_ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::Make {
+            payer: maker.pubkey(),
+            maker: maker.pubkey(),
+            config: f.config(),
+            escrow,
+            registry: f.registry(&maker.pubkey()),
+            maker_stats: f.maker_stats(&maker.pubkey()),
+            pair_stats: f.pair_stats(),
+            mint_a: f.mint_a.pubkey(),
+            mint_b: f.mint_b.pubkey(),
+            mint_a_rule: mint_rule_address(&f.mint_a.pubkey()),
+            mint_b_rule: mint_rule_address(&f.mint_b.pubkey()),
+            maker_ata_a: ata(&maker.pubkey(), &f.mint_a.pubkey()),
+            vault: ata(&escrow, &f.mint_a.pubkey()),
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::Make {
+            seed,
+            receive,
+            amount,
+        }
+        .data(),
+    };
+    let submit_ix = submit_quote_ix(f, &maker.pubkey(), seed);
+    f.send(&[make_ix, submit_ix], &[maker])
+}
+
+fn accept_quote(f: &mut EscrowFixture, winner: &Pubkey, losers: &[Pubkey]) -> TransactionResult {
+    let escrow = escrow_of(winner, SEED);
+    let taker = f.taker.pubkey();
+    let mut accounts = anchor_escrow::accounts::AcceptQuote {
+        taker,
+        config: f.config(),
+        rfq: rfq(f),
+        maker: *winner,
+        escrow,
+        registry: f.registry(winner),
+        maker_stats: f.maker_stats(winner),
+        mint_a: f.mint_a.pubkey(),
+        mint_b: f.mint_b.pubkey(),
+        pair_stats: f.pair_stats(),
+        vault: ata(&escrow, &f.mint_a.pubkey()),
+        taker_ata_a: ata(&taker, &f.mint_a.pubkey()),
+        taker_ata_b: f.taker_ata_b,
+        maker_ata_b: ata(winner, &f.mint_b.pubkey()),
+        associated_token_program: spl_associated_token_account::id(),
+        token_program: spl_token::id(),
+        system_program: anchor_lang::system_program::ID,
+    }
+    .to_account_metas(None);
+
+    for loser in losers {
+        let escrow = escrow_of(loser, SEED);
+        accounts.extend([
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(ata(&escrow, &f.mint_a.pubkey()), false),
+            AccountMeta::new(*loser, false),
+            AccountMeta::new(ata(loser, &f.mint_a.pubkey()), false),
+            AccountMeta::new(f.registry(loser), false),
+            AccountMeta::new(f.maker_stats(loser), false),
+        ]);
+    }
+
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts,
+        data: anchor_escrow::instruction::AcceptQuote {}.data(),
+    };
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+fn withdraw_quote(f: &mut EscrowFixture, maker: &Keypair) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::WithdrawQuote {
+            maker: maker.pubkey(),
+            escrow: escrow_of(&maker.pubkey(), SEED),
+            rfq: rfq(f),
+            taker: f.taker.pubkey(),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::WithdrawQuote {}.data(),
+    };
+    f.send(&[ix], &[maker])
+}
+
+fn cancel_rfq(f: &mut EscrowFixture) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::CancelRfq {
+            taker: f.taker.pubkey(),
+            rfq: rfq(f),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::CancelRfq {}.data(),
+    };
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+#[test]
+fn test_accepting_a_quote_refunds_the_others() {
+    let mut f = EscrowFixture::new();
+    open_rfq(&mut f, AMOUNT);
+
+    let first = f.maker.insecure_clone();
+    let second = new_maker(&mut f);
+    let third = new_maker(&mut f);
+    quote(&mut f, &first, SEED, 450_000_000, AMOUNT).unwrap();
+    quote(&mut f, &second, SEED, 400_000_000, AMOUNT).unwrap();
+    quote(&mut f, &third, SEED, 480_000_000, AMOUNT).unwrap();
+
+    let state = rfq_state(&f);
+    assert_eq!(state.status, RfqStatus::Open);
+    assert_eq!(state.quotes.len(), 3);
+    assert_eq!(f.escrow_state(SEED).rfq(), Some(rfq(&f)));
+
+    accept_quote(&mut f, &second.pubkey(), &[first.pubkey(), third.pubkey()]).unwrap();
+
+    assert!(f.is_closed(&rfq(&f)));
+    let taker_ata_a = ata(&f.taker.pubkey(), &f.mint_a.pubkey());
+    assert_eq!(f.token_balance(&taker_ata_a), AMOUNT);
+    assert_eq!(f.token_balance(&f.taker_ata_b), RECEIVE - 400_000_000);
+    assert_eq!(
+        f.token_balance(&ata(&second.pubkey(), &f.mint_b.pubkey())),
+        400_000_000
+    );
+
+    for maker in [&first, &second, &third] {
+        assert!(f.is_closed(&escrow_of(&maker.pubkey(), SEED)));
+        assert!(registry_state(&f, &maker.pubkey()).escrows.is_empty());
+    }
+    for loser in [&first, &third] {
+        assert_eq!(
+            f.token_balance(&ata(&loser.pubkey(), &f.mint_a.pubkey())),
+            AMOUNT
+        );
+        let stats = f.maker_stats_state(&loser.pubkey());
+        assert_eq!(stats.counts, counts(1, 0, 1));
+    }
+    let stats = f.maker_stats_state(&second.pubkey());
+    assert_eq!(stats.counts, counts(1, 1, 0));
+    let pair = f.pair_stats_state();
+    assert_eq!(pair.counts, counts(3, 1, 2));
+    assert_eq!((pair.volume_a, pair.volume_b), (AMOUNT.into(), 400_000_000));
+}
+
+#[test]
+fn test_accept_requires_every_losing_quote() {
+    let mut f = EscrowFixture::new();
+    open_rfq(&mut f, AMOUNT);
+    let first = f.maker.insecure_clone();
+    let second = new_maker(&mut f);
+    quote(&mut f, &first, SEED, RECEIVE, AMOUNT).unwrap();
+    quote(&mut f, &second, SEED, RECEIVE, AMOUNT).unwrap();
+
+    assert_escrow_error(
+        accept_quote(&mut f, &second.pubkey(), &[]),
+        EscrowError::QuoteNotFound,
+    );
+    assert_escrow_error(
+        accept_quote(&mut f, &second.pubkey(), &[first.pubkey(), first.pubkey()]),
+        EscrowError::QuoteNotFound,
+    );
+}
+
+#[test]
+fn test_quoted_escrow_is_locked_to_the_rfq() {
+    let mut f = EscrowFixture::new();
+    open_rfq(&mut f, AMOUNT);
+    let maker = f.maker.insecure_clone();
+    quote(&mut f, &maker, SEED, RECEIVE, AMOUNT).unwrap();
+
+    assert_escrow_error(f.take(SEED), EscrowError::EscrowQuoted);
+    assert_escrow_error(f.refund(SEED), EscrowError::EscrowQuoted);
+
+    withdraw_quote(&mut f, &maker).unwrap();
+    assert!(rfq_state(&f).quotes.is_empty());
+    assert_eq!(f.escrow_state(SEED).rfq(), None);
+    f.refund(SEED).unwrap();
+}
+
+#[test]
+fn test_quoted_escrow_keeps_its_terms() {
+    let mut f = EscrowFixture::new();
+    open_rfq(&mut f, AMOUNT);
+    let maker = f.maker.insecure_clone();
+    let terms_ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetReservationTerms {
+            maker: maker.pubkey(),
+            escrow: f.escrow(SEED),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetReservationTerms {
+            terms: Some(ReservationTerms {
+                bond: 1_000_000,
+                window: 50,
+            }),
+        }
+        .data(),
+    };
+    let make_ix = f.make_ix(SEED, RECEIVE, AMOUNT);
+    let submit_ix = submit_quote_ix(&f, &maker.pubkey(), SEED);
+    f.send(&[make_ix, terms_ix, submit_ix], &[&maker]).unwrap();
+
+    // Restrictions added after quoting would be skipped by accept_quote
+    let vesting_ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetVesting {
+            maker: maker.pubkey(),
+            escrow: f.escrow(SEED),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetVesting {
+            schedule: Some(VestingSchedule {
+                cliff: 0,
+                duration: 100,
+            }),
+        }
+        .data(),
+    };
+    assert_escrow_error(f.send(&[vesting_ix], &[&maker]), EscrowError::EscrowQuoted);
+
+    // As would a reservation, whose bond accept_quote would leave behind
+    let taker = f.taker.insecure_clone();
+    let reserve_ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::Reserve {
+            taker: taker.pubkey(),
+            escrow: f.escrow(SEED),
+            reservation: Pubkey::find_program_address(
+                &[b"reservation", f.escrow(SEED).as_ref()],
+                &anchor_escrow::ID,
+            )
+            .0,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::Reserve {}.data(),
+    };
+    assert_escrow_error(f.send(&[reserve_ix], &[&taker]), EscrowError::EscrowQuoted);
+}
+
+#[test]
+fn test_quotes_must_cover_the_requested_size() {
+    let mut f = EscrowFixture::new();
+    open_rfq(&mut f, AMOUNT);
+    let maker = f.maker.insecure_clone();
+
+    assert_escrow_error(
+        quote(&mut f, &maker, SEED, RECEIVE, AMOUNT - 1),
+        EscrowError::QuoteDoesNotMatchRfq,
+    );
+    quote(&mut f, &maker, SEED, RECEIVE, AMOUNT).unwrap();
+}
+
+#[test]
+fn test_one_quote_per_maker() {
+    let mut f = EscrowFixture::new();
+    open_rfq(&mut f, AMOUNT / 2);
+    let maker = f.maker.insecure_clone();
+
+    quote(&mut f, &maker, SEED, RECEIVE / 2, AMOUNT / 2).unwrap();
+    assert_escrow_error(
+        quote(&mut f, &maker, SEED + 1, RECEIVE / 2, AMOUNT / 2),
+        EscrowError::DuplicateQuote,
+    );
+}
+
+#[test]
+fn test_cancelled_rfq_closes_after_last_withdrawal() {
+    let mut f = EscrowFixture::new();
+    open_rfq(&mut f, AMOUNT);
+    let first = f.maker.insecure_clone();
+    let second = new_maker(&mut f);
+    quote(&mut f, &first, SEED, RECEIVE, AMOUNT).unwrap();
+
+    cancel_rfq(&mut f).unwrap();
+    assert_eq!(rfq_state(&f).status, RfqStatus::Cancelled);
+    assert_escrow_error(
+        quote(&mut f, &second, SEED, RECEIVE, AMOUNT),
+        EscrowError::RfqNotOpen,
+    );
+    assert_escrow_error(
+        accept_quote(&mut f, &first.pubkey(), &[]),
+        EscrowError::RfqNotOpen,
+    );
+
+    withdraw_quote(&mut f, &first).unwrap();
+    assert!(f.is_closed(&rfq(&f)));
+    f.refund(SEED).unwrap();
+}
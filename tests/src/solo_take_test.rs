@@ -0,0 +1,108 @@
+use crate::common::{assert_escrow_error, take_instruction, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use anchor_escrow::errors::EscrowError;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::types::TransactionResult;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Signer, sysvar,
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+fn set_solo_take_ix(f: &EscrowFixture, enabled: bool) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetSoloTake {
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetSoloTake { enabled }.data(),
+    }
+}
+
+fn make_solo(f: &mut EscrowFixture) {
+    let make_ix = f.make_ix(SEED, RECEIVE, AMOUNT);
+    let solo_ix = set_solo_take_ix(f, true);
+    let maker = f.maker.insecure_clone();
+    f.send(&[make_ix, solo_ix], &[&maker]).unwrap();
+}
+
+fn solo_take_ix(f: &EscrowFixture) -> Instruction {
+    let mut accounts = f.take_accounts(SEED, &f.taker.pubkey(), &f.maker.pubkey());
+    accounts.instructions_sysvar = Some(sysvar::instructions::ID);
+    take_instruction(accounts, vec![])
+}
+
+// Sends the take wrapped in `before` and `after`
+fn take_between(
+    f: &mut EscrowFixture,
+    before: &[Instruction],
+    after: &[Instruction],
+) -> TransactionResult {
+    let mut ixs = before.to_vec();
+    ixs.push(solo_take_ix(f));
+    ixs.extend_from_slice(after);
+    let taker = f.taker.insecure_clone();
+    f.send(&ixs, &[&taker])
+}
+
+// A harmless instruction from another program
+fn open_ata(f: &EscrowFixture) -> Instruction {
+    create_associated_token_account_idempotent(
+        &f.taker.pubkey(),
+        &f.taker.pubkey(),
+        &f.mint_a.pubkey(),
+        &spl_token::id(),
+    )
+}
+
+#[test]
+fn test_solo_take_fills_alone() {
+    let mut f = EscrowFixture::new();
+    make_solo(&mut f);
+    assert!(f.escrow_state(SEED).solo_take());
+
+    // Compute budget settings can't move a market, so they are let through
+    let budget = [
+        ComputeBudgetInstruction::set_compute_unit_limit(400_000),
+        ComputeBudgetInstruction::set_compute_unit_price(1),
+    ];
+    take_between(&mut f, &budget, &[]).unwrap();
+    assert!(f.is_closed(&f.escrow(SEED)));
+}
+
+#[test]
+fn test_other_programs_before_or_after_are_rejected() {
+    let mut f = EscrowFixture::new();
+    make_solo(&mut f);
+
+    let before = [open_ata(&f)];
+    assert_escrow_error(
+        take_between(&mut f, &before, &[]),
+        EscrowError::TakeNotAlone,
+    );
+    let after = [open_ata(&f)];
+    assert_escrow_error(take_between(&mut f, &[], &after), EscrowError::TakeNotAlone);
+}
+
+#[test]
+fn test_solo_take_needs_the_instructions_sysvar() {
+    let mut f = EscrowFixture::new();
+    make_solo(&mut f);
+    assert_escrow_error(f.take(SEED), EscrowError::InstructionsSysvarMissing);
+}
+
+#[test]
+fn test_guard_is_opt_in() {
+    let mut f = EscrowFixture::new();
+    make_solo(&mut f);
+    let ix = set_solo_take_ix(&f, false);
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+
+    let ixs = [
+        open_ata(&f),
+        f.take_ix(SEED, &f.taker.pubkey(), &f.maker.pubkey()),
+    ];
+    let taker = f.taker.insecure_clone();
+    f.send(&ixs, &[&taker]).unwrap();
+}
@@ -0,0 +1,209 @@
+use crate::common::{assert_escrow_error, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use crate::mock_oracle::set_unix_timestamp;
+use anchor_escrow::{
+    errors::EscrowError,
+    state::{ReservationTerms, StartTime},
+};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_sdk::{clock::Clock, instruction::Instruction, pubkey::Pubkey, signature::Signer};
+
+const DELAY: u64 = 100;
+
+fn set_start_time_ix(f: &EscrowFixture, start: Option<StartTime>) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetStartTime {
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetStartTime { start }.data(),
+    }
+}
+
+// Makes the escrow and sets its start in one transaction, as a launch would
+fn make_starting_at(f: &mut EscrowFixture, start: StartTime) {
+    let make_ix = f.make_ix(SEED, RECEIVE, AMOUNT);
+    let start_ix = set_start_time_ix(f, Some(start));
+    let maker = f.maker.insecure_clone();
+    f.send(&[make_ix, start_ix], &[&maker]).unwrap();
+}
+
+fn now(f: &EscrowFixture) -> i64 {
+    f.ctx.svm.get_sysvar::<Clock>().unix_timestamp
+}
+
+fn slot(f: &EscrowFixture) -> u64 {
+    f.ctx.svm.get_sysvar::<Clock>().slot
+}
+
+#[test]
+fn test_start_time_is_stored() {
+    let mut f = EscrowFixture::new();
+    let start = StartTime::UnixTimestamp(now(&f) + DELAY as i64);
+    make_starting_at(&mut f, start);
+
+    assert_eq!(f.escrow_state(SEED).start_time(), Some(start));
+}
+
+#[test]
+fn test_take_waits_for_unix_start() {
+    let mut f = EscrowFixture::new();
+    let start = now(&f) + DELAY as i64;
+    make_starting_at(&mut f, StartTime::UnixTimestamp(start));
+
+    set_unix_timestamp(&mut f.ctx.svm, start - 1);
+    assert_escrow_error(f.take(SEED), EscrowError::NotStarted);
+
+    set_unix_timestamp(&mut f.ctx.svm, start);
+    f.take(SEED).unwrap();
+    assert!(f.is_closed(&f.escrow(SEED)));
+}
+
+#[test]
+fn test_take_waits_for_start_slot() {
+    let mut f = EscrowFixture::new();
+    let start = slot(&f) + DELAY;
+    make_starting_at(&mut f, StartTime::Slot(start));
+
+    f.ctx.svm.warp_to_slot(start - 1);
+    assert_escrow_error(f.take(SEED), EscrowError::NotStarted);
+
+    f.ctx.svm.warp_to_slot(start);
+    f.take(SEED).unwrap();
+    assert!(f.is_closed(&f.escrow(SEED)));
+}
+
+#[test]
+fn test_refund_before_start() {
+    let mut f = EscrowFixture::new();
+    let start = now(&f) + DELAY as i64;
+    make_starting_at(&mut f, StartTime::UnixTimestamp(start));
+
+    f.refund(SEED).unwrap();
+
+    assert!(f.is_closed(&f.escrow(SEED)));
+    assert_eq!(f.token_balance(&f.maker_ata_a), AMOUNT);
+}
+
+#[test]
+fn test_clearing_start_time_opens_escrow() {
+    let mut f = EscrowFixture::new();
+    let start = now(&f) + DELAY as i64;
+    make_starting_at(&mut f, StartTime::UnixTimestamp(start));
+
+    let clear_ix = set_start_time_ix(&f, None);
+    let maker = f.maker.insecure_clone();
+    f.send(&[clear_ix], &[&maker]).unwrap();
+
+    assert_eq!(f.escrow_state(SEED).start_time(), None);
+    f.take(SEED).unwrap();
+}
+
+#[test]
+fn test_only_maker_sets_start_time() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    let mut ix = set_start_time_ix(&f, Some(StartTime::Slot(slot(&f) + DELAY)));
+    ix.accounts[0].pubkey = f.taker.pubkey();
+    let taker = f.taker.insecure_clone();
+
+    assert_escrow_error(f.send(&[ix], &[&taker]), EscrowError::InvalidMaker);
+}
+
+#[test]
+fn test_start_time_is_fixed_while_reserved() {
+    let mut f = EscrowFixture::new();
+    let escrow = f.escrow(SEED);
+    let reservation =
+        Pubkey::find_program_address(&[b"reservation", escrow.as_ref()], &anchor_escrow::ID).0;
+    let terms_ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetReservationTerms {
+            maker: f.maker.pubkey(),
+            escrow,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetReservationTerms {
+            terms: Some(ReservationTerms {
+                bond: 100_000_000,
+                window: 50,
+            }),
+        }
+        .data(),
+    };
+    let make_ix = f.make_ix(SEED, RECEIVE, AMOUNT);
+    let maker = f.maker.insecure_clone();
+    f.send(&[make_ix, terms_ix], &[&maker]).unwrap();
+    let reserve_ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::Reserve {
+            taker: f.taker.pubkey(),
+            escrow,
+            reservation,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::Reserve {}.data(),
+    };
+    let taker = f.taker.insecure_clone();
+    f.send(&[reserve_ix], &[&taker]).unwrap();
+
+    // A later start would run the reservation window out before the taker
+    // could fill, handing the maker their bond
+    let start = StartTime::UnixTimestamp(now(&f) + DELAY as i64);
+    let ix = set_start_time_ix(&f, Some(start));
+    assert_escrow_error(f.send(&[ix], &[&maker]), EscrowError::EscrowReserved);
+}
+
+#[test]
+fn test_start_time_is_fixed_while_quoted() {
+    let mut f = EscrowFixture::new();
+    let rfq_seed = 7u64;
+    let rfq = Pubkey::find_program_address(
+        &[b"rfq", f.taker.pubkey().as_ref(), &rfq_seed.to_le_bytes()],
+        &anchor_escrow::ID,
+    )
+    .0;
+    let open_ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::OpenRfq {
+            taker: f.taker.pubkey(),
+            rfq,
+            mint_a: f.mint_a.pubkey(),
+            mint_b: f.mint_b.pubkey(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::OpenRfq {
+            seed: rfq_seed,
+            amount: AMOUNT,
+        }
+        .data(),
+    };
+    let taker = f.taker.insecure_clone();
+    f.send(&[open_ix], &[&taker]).unwrap();
+    let make_ix = f.make_ix(SEED, RECEIVE, AMOUNT);
+    let quote_ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SubmitQuote {
+            maker: f.maker.pubkey(),
+            config: f.config(),
+            escrow: f.escrow(SEED),
+            vault: f.vault(SEED),
+            rfq,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SubmitQuote {}.data(),
+    };
+    let maker = f.maker.insecure_clone();
+    f.send(&[make_ix, quote_ix], &[&maker]).unwrap();
+
+    // accept_quote fills without checking the start, so the quote is what
+    // the taker is promised
+    let start = StartTime::UnixTimestamp(now(&f) + DELAY as i64);
+    let ix = set_start_time_ix(&f, Some(start));
+    assert_escrow_error(f.send(&[ix], &[&maker]), EscrowError::EscrowQuoted);
+}
@@ -0,0 +1,136 @@
+use crate::common::{counts, EscrowFixture, AMOUNT, RECEIVE};
+use anchor_escrow::state::EscrowCounts;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_litesvm::TestHelpers;
+use solana_sdk::{instruction::Instruction, signature::Signer};
+
+// Splits the maker's AMOUNT across `seeds`, each asking `receive`
+fn make_several(f: &mut EscrowFixture, seeds: &[u64], receive: u64) {
+    let amount = AMOUNT / seeds.len() as u64;
+    let ixs: Vec<Instruction> = seeds
+        .iter()
+        .map(|&seed| f.make_ix(seed, receive, amount))
+        .collect();
+    let maker = f.maker.insecure_clone();
+    f.send(&ixs, &[&maker]).unwrap();
+}
+
+#[test]
+fn test_registry_starts_stats_at_zero() {
+    let f = EscrowFixture::new();
+    let stats = f.maker_stats_state(&f.maker.pubkey());
+    assert_eq!(stats.maker, f.maker.pubkey());
+    assert_eq!(stats.counts, EscrowCounts::default());
+    // The market's stats wait for its first make
+    assert!(f.ctx.svm.get_account(&f.pair_stats()).is_none());
+}
+
+#[test]
+fn test_counters_follow_make_take_and_refund() {
+    let mut f = EscrowFixture::new();
+    make_several(&mut f, &[1, 2, 3, 4], RECEIVE / 4);
+    f.take(1).unwrap();
+    f.refund(2).unwrap();
+    f.take(3).unwrap();
+
+    assert_eq!(
+        f.maker_stats_state(&f.maker.pubkey()).counts,
+        counts(4, 2, 1)
+    );
+    let pair = f.pair_stats_state();
+    assert_eq!(
+        (pair.mint_a, pair.mint_b),
+        (f.mint_a.pubkey(), f.mint_b.pubkey())
+    );
+    assert_eq!(pair.counts, counts(4, 2, 1));
+    assert_eq!(pair.volume_a, u128::from(AMOUNT / 2));
+    assert_eq!(pair.volume_b, u128::from(RECEIVE / 2));
+}
+
+#[test]
+fn test_refund_all_counts_every_refund() {
+    let mut f = EscrowFixture::new();
+    make_several(&mut f, &[1, 2, 3], RECEIVE / 4);
+    f.take(2).unwrap();
+
+    let ix = f.refund_all_ix(&[1, 3]);
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+
+    assert_eq!(
+        f.maker_stats_state(&f.maker.pubkey()).counts,
+        counts(3, 1, 2)
+    );
+    assert_eq!(f.pair_stats_state().counts, counts(3, 1, 2));
+}
+
+#[test]
+fn test_last_fill_price_tracks_the_latest_take() {
+    let mut f = EscrowFixture::new();
+    let ixs = [
+        f.make_ix(1, RECEIVE / 5, AMOUNT / 2),
+        f.make_ix(2, RECEIVE / 2, AMOUNT / 4),
+    ];
+    let maker = f.maker.insecure_clone();
+    f.send(&ixs, &[&maker]).unwrap();
+
+    f.take(1).unwrap();
+    let pair = f.pair_stats_state();
+    assert_eq!(
+        (pair.last_fill_a, pair.last_fill_b),
+        (AMOUNT / 2, RECEIVE / 5)
+    );
+
+    f.take(2).unwrap();
+    let pair = f.pair_stats_state();
+    assert_eq!(
+        (pair.last_fill_a, pair.last_fill_b),
+        (AMOUNT / 4, RECEIVE / 2)
+    );
+    assert_eq!(pair.volume_a, u128::from(AMOUNT / 2 + AMOUNT / 4));
+    assert_eq!(pair.volume_b, u128::from(RECEIVE / 5 + RECEIVE / 2));
+}
+
+#[test]
+fn test_transferred_escrow_counts_toward_the_new_maker() {
+    let mut f = EscrowFixture::new();
+    let new_maker = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    f.init_registry(&new_maker.pubkey()).unwrap();
+    make_several(&mut f, &[1, 2], RECEIVE / 2);
+
+    for seed in [1, 2] {
+        let ix = Instruction {
+            program_id: anchor_escrow::ID,
+            accounts: anchor_escrow::accounts::TransferEscrow {
+                maker: f.maker.pubkey(),
+                new_maker: new_maker.pubkey(),
+                escrow: f.escrow(seed),
+                registry: f.registry(&f.maker.pubkey()),
+                new_registry: f.registry(&new_maker.pubkey()),
+                new_maker_stats: f.maker_stats(&new_maker.pubkey()),
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: anchor_escrow::instruction::TransferEscrow {}.data(),
+        };
+        let maker = f.maker.insecure_clone();
+        f.send(&[ix], &[&maker]).unwrap();
+    }
+
+    let ix = f.take_ix(1, &f.taker.pubkey(), &new_maker.pubkey());
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker]).unwrap();
+    let ix = f.refund_ix(2, &new_maker.pubkey());
+    f.send(&[ix], &[&new_maker]).unwrap();
+
+    // Creation stays with the original maker, the outcomes go to the new one
+    assert_eq!(
+        f.maker_stats_state(&f.maker.pubkey()).counts,
+        counts(2, 0, 0)
+    );
+    assert_eq!(
+        f.maker_stats_state(&new_maker.pubkey()).counts,
+        counts(0, 1, 1)
+    );
+    assert_eq!(f.pair_stats_state().counts, counts(2, 1, 1));
+}
@@ -0,0 +1,141 @@
+use crate::common::{
+    assert_escrow_error, make_instruction, pair_stats_address, take_instruction_with_cleanup,
+    EscrowFixture, AMOUNT, RECEIVE, SEED,
+};
+use anchor_escrow::errors::EscrowError;
+use anchor_escrow::TakeCleanup;
+use litesvm::types::TransactionResult;
+use solana_program_pack::Pack;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
+use spl_token::native_mint;
+
+fn take_with_cleanup(f: &mut EscrowFixture, cleanup: TakeCleanup) -> TransactionResult {
+    let accounts = f.take_accounts(SEED, &f.taker.pubkey(), &f.maker.pubkey());
+    let ix = take_instruction_with_cleanup(accounts, vec![], cleanup);
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+// Makes an escrow that sells AMOUNT lamports of wrapped SOL for mint_b
+fn make_wsol_escrow(f: &mut EscrowFixture) {
+    let maker = f.maker.pubkey();
+    let maker_wsol = get_associated_token_address(&maker, &native_mint::ID);
+    let create_ix =
+        create_associated_token_account(&maker, &maker, &native_mint::ID, &spl_token::id());
+    let maker_kp = f.maker.insecure_clone();
+    f.send(&[create_ix], &[&maker_kp]).unwrap();
+    f.ctx.svm.airdrop(&maker_wsol, AMOUNT).unwrap();
+    let sync_ix = spl_token::instruction::sync_native(&spl_token::id(), &maker_wsol).unwrap();
+
+    let mut accounts = f.make_accounts(SEED);
+    accounts.pair_stats = pair_stats_address(&native_mint::ID, &f.mint_b.pubkey());
+    accounts.mint_a = native_mint::ID;
+    accounts.maker_ata_a = maker_wsol;
+    accounts.vault = wsol_vault(&f.escrow(SEED));
+    let make_ix = make_instruction(accounts, SEED, RECEIVE, AMOUNT);
+    f.send(&[sync_ix, make_ix], &[&maker_kp]).unwrap();
+}
+
+fn wsol_vault(escrow: &Pubkey) -> Pubkey {
+    get_associated_token_address(escrow, &native_mint::ID)
+}
+
+fn take_wsol_escrow(f: &mut EscrowFixture, cleanup: TakeCleanup) -> TransactionResult {
+    let taker = f.taker.pubkey();
+    let mut accounts = f.take_accounts(SEED, &taker, &f.maker.pubkey());
+    accounts.mint_a = native_mint::ID;
+    accounts.pair_stats = pair_stats_address(&native_mint::ID, &f.mint_b.pubkey());
+    accounts.vault = wsol_vault(&f.escrow(SEED));
+    accounts.taker_ata_a = get_associated_token_address(&taker, &native_mint::ID);
+    let ix = take_instruction_with_cleanup(accounts, vec![], cleanup);
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+#[test]
+fn test_take_closes_emptied_taker_ata_b() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    let cleanup = TakeCleanup {
+        close_taker_ata_b: true,
+        ..Default::default()
+    };
+    take_with_cleanup(&mut f, cleanup).unwrap();
+
+    assert!(f.is_closed(&f.taker_ata_b));
+}
+
+#[test]
+fn test_take_keeps_taker_ata_b_with_a_balance() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    let mint_to_ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &f.mint_b.pubkey(),
+        &f.taker_ata_b,
+        &f.maker.pubkey(),
+        &[],
+        1,
+    )
+    .unwrap();
+    let maker = f.maker.insecure_clone();
+    f.send(&[mint_to_ix], &[&maker]).unwrap();
+
+    let cleanup = TakeCleanup {
+        close_taker_ata_b: true,
+        ..Default::default()
+    };
+    take_with_cleanup(&mut f, cleanup).unwrap();
+
+    assert_eq!(f.token_balance(&f.taker_ata_b), 1);
+}
+
+#[test]
+fn test_take_leaves_taker_ata_b_open_by_default() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    f.take(SEED).unwrap();
+
+    assert_eq!(f.token_balance(&f.taker_ata_b), 0);
+}
+
+#[test]
+fn test_take_unwraps_wsol_proceeds() {
+    let mut f = EscrowFixture::new();
+    make_wsol_escrow(&mut f);
+    let before = f.ctx.svm.get_balance(&f.taker.pubkey()).unwrap();
+
+    let cleanup = TakeCleanup {
+        unwrap_sol: true,
+        ..Default::default()
+    };
+    take_wsol_escrow(&mut f, cleanup).unwrap();
+
+    // The taker_ata_a rent comes straight back, leaving the fee and the
+    // maker_ata_b rent as the taker's only costs
+    let after = f.ctx.svm.get_balance(&f.taker.pubkey()).unwrap();
+    let maker_ata_b_rent = f
+        .ctx
+        .svm
+        .minimum_balance_for_rent_exemption(spl_token::state::Account::LEN);
+    assert_eq!(after + maker_ata_b_rent + 5_000, before + AMOUNT);
+    let taker_wsol = get_associated_token_address(&f.taker.pubkey(), &native_mint::ID);
+    assert!(f.is_closed(&taker_wsol));
+}
+
+#[test]
+fn test_unwrap_sol_rejects_other_mints() {
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    let cleanup = TakeCleanup {
+        unwrap_sol: true,
+        ..Default::default()
+    };
+    assert_escrow_error(
+        take_with_cleanup(&mut f, cleanup),
+        EscrowError::NotWrappedSol,
+    );
+}
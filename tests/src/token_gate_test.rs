@@ -0,0 +1,125 @@
+use crate::common::{assert_escrow_error, take_instruction, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use anchor_escrow::errors::EscrowError;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_litesvm::TestHelpers;
+use litesvm::types::TransactionResult;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const MIN_GATE_BALANCE: u64 = 5;
+
+fn set_gate_ix(f: &EscrowFixture, gate_mint: Option<Pubkey>, min_gate_balance: u64) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetGate {
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetGate {
+            gate_mint,
+            min_gate_balance,
+        }
+        .data(),
+    }
+}
+
+fn take_with_gate(f: &mut EscrowFixture, gate_account: Option<Pubkey>) -> TransactionResult {
+    let mut accounts = f.take_accounts(SEED, &f.taker.pubkey(), &f.maker.pubkey());
+    accounts.taker_gate_account = gate_account;
+    let ix = take_instruction(accounts, vec![]);
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+// Makes a gated escrow and hands the taker `balance` membership tokens
+fn make_gated(f: &mut EscrowFixture, balance: u64) -> (Keypair, Pubkey) {
+    let maker = f.maker.insecure_clone();
+    let taker = f.taker.insecure_clone();
+    let gate_mint = f.ctx.create_token_mint(&maker, 0).unwrap();
+    let gate_account = f
+        .ctx
+        .create_token_account(&taker, &gate_mint.pubkey(), Some((balance, &maker)))
+        .unwrap();
+
+    let make_ix = f.make_ix(SEED, RECEIVE, AMOUNT);
+    let set_ix = set_gate_ix(f, Some(gate_mint.pubkey()), MIN_GATE_BALANCE);
+    f.send(&[make_ix, set_ix], &[&maker]).unwrap();
+
+    (gate_mint, gate_account)
+}
+
+#[test]
+fn test_holder_can_take() {
+    let mut f = EscrowFixture::new();
+    let (gate_mint, gate_account) = make_gated(&mut f, MIN_GATE_BALANCE);
+
+    let escrow = f.escrow_state(SEED);
+    assert_eq!(escrow.gate_mint(), Some(gate_mint.pubkey()));
+    assert_eq!({ escrow.min_gate_balance }, MIN_GATE_BALANCE);
+
+    take_with_gate(&mut f, Some(gate_account)).unwrap();
+    assert!(f.is_closed(&f.escrow(SEED)));
+    // Holding the gate token is enough, it isn't spent
+    assert_eq!(f.token_balance(&gate_account), MIN_GATE_BALANCE);
+}
+
+#[test]
+fn test_insufficient_gate_balance_is_rejected() {
+    let mut f = EscrowFixture::new();
+    let (_, gate_account) = make_gated(&mut f, MIN_GATE_BALANCE - 1);
+
+    assert_escrow_error(
+        take_with_gate(&mut f, Some(gate_account)),
+        EscrowError::InsufficientGateBalance,
+    );
+}
+
+#[test]
+fn test_wrong_gate_mint_is_rejected() {
+    let mut f = EscrowFixture::new();
+    make_gated(&mut f, MIN_GATE_BALANCE);
+
+    // The taker's mint_b account has plenty of tokens, but of the wrong mint
+    let taker_ata_b = f.taker_ata_b;
+    assert_escrow_error(
+        take_with_gate(&mut f, Some(taker_ata_b)),
+        EscrowError::InvalidGateMint,
+    );
+}
+
+#[test]
+fn test_gate_account_of_someone_else_is_rejected() {
+    let mut f = EscrowFixture::new();
+    let (gate_mint, _) = make_gated(&mut f, 0);
+
+    let maker = f.maker.insecure_clone();
+    let holder = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    let borrowed = f
+        .ctx
+        .create_token_account(
+            &holder,
+            &gate_mint.pubkey(),
+            Some((MIN_GATE_BALANCE, &maker)),
+        )
+        .unwrap();
+
+    assert_escrow_error(
+        take_with_gate(&mut f, Some(borrowed)),
+        EscrowError::InvalidGateOwner,
+    );
+}
+
+#[test]
+fn test_missing_gate_account_is_rejected() {
+    let mut f = EscrowFixture::new();
+    make_gated(&mut f, MIN_GATE_BALANCE);
+
+    assert_escrow_error(
+        take_with_gate(&mut f, None),
+        EscrowError::GateAccountMissing,
+    );
+}
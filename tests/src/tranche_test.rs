@@ -0,0 +1,225 @@
+use crate::common::{assert_escrow_error, counts, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use crate::mock_oracle::set_unix_timestamp;
+use anchor_escrow::{errors::EscrowError, state::MakerRegistry, tranche::TrancheSchedule};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use litesvm::types::TransactionResult;
+use solana_sdk::{clock::Clock, instruction::Instruction, signature::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+const DAY: i64 = 86_400;
+
+fn daily(receive: Option<u64>) -> TrancheSchedule {
+    TrancheSchedule {
+        size: AMOUNT / 4,
+        interval: DAY,
+        receive,
+    }
+}
+
+fn set_tranches_ix(f: &EscrowFixture, schedule: Option<TrancheSchedule>) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetTranches {
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetTranches { schedule }.data(),
+    }
+}
+
+fn make_tranched(f: &mut EscrowFixture, schedule: TrancheSchedule) {
+    let make_ix = f.make_ix(SEED, RECEIVE, AMOUNT);
+    let set_ix = set_tranches_ix(f, Some(schedule));
+    let maker = f.maker.insecure_clone();
+    f.send(&[make_ix, set_ix], &[&maker]).unwrap();
+}
+
+fn take_tranche(f: &mut EscrowFixture) -> TransactionResult {
+    take_tranche_with_limit(f, u64::MAX)
+}
+
+fn take_tranche_with_limit(f: &mut EscrowFixture, max_receive: u64) -> TransactionResult {
+    let taker = f.taker.pubkey();
+    let maker = f.maker.pubkey();
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::TakeTranche {
+            taker,
+            maker,
+            config: f.config(),
+            escrow: f.escrow(SEED),
+            registry: f.registry(&maker),
+            maker_stats: f.maker_stats(&maker),
+            pair_stats: f.pair_stats(),
+            mint_a: f.mint_a.pubkey(),
+            mint_b: f.mint_b.pubkey(),
+            vault: f.vault(SEED),
+            taker_ata_a: get_associated_token_address(&taker, &f.mint_a.pubkey()),
+            taker_ata_b: f.taker_ata_b,
+            maker_ata_b: get_associated_token_address(&maker, &f.mint_b.pubkey()),
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::TakeTranche { max_receive }.data(),
+    };
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+fn advance(f: &mut EscrowFixture, seconds: i64) {
+    let now = f.ctx.svm.get_sysvar::<Clock>().unix_timestamp;
+    set_unix_timestamp(&mut f.ctx.svm, now + seconds);
+}
+
+fn maker_balance_b(f: &EscrowFixture) -> u64 {
+    f.token_balance(&get_associated_token_address(
+        &f.maker.pubkey(),
+        &f.mint_b.pubkey(),
+    ))
+}
+
+#[test]
+fn test_tranches_fill_once_per_interval_at_escrow_price() {
+    let mut f = EscrowFixture::new();
+    make_tranched(&mut f, daily(None));
+
+    take_tranche(&mut f).unwrap();
+    assert_eq!(f.token_balance(&f.vault(SEED)), AMOUNT * 3 / 4);
+    assert_eq!(maker_balance_b(&f), RECEIVE / 4);
+    assert_escrow_error(take_tranche(&mut f), EscrowError::TrancheNotReady);
+
+    advance(&mut f, DAY - 1);
+    assert_escrow_error(take_tranche(&mut f), EscrowError::TrancheNotReady);
+    advance(&mut f, 1);
+    take_tranche(&mut f).unwrap();
+
+    let tranches = f.escrow_state(SEED).tranches().unwrap();
+    assert_eq!(tranches.index, 2);
+    assert_eq!(
+        tranches.last_fill,
+        f.ctx.svm.get_sysvar::<Clock>().unix_timestamp
+    );
+    assert_eq!({ f.escrow_state(SEED).receive }, RECEIVE / 2);
+
+    for _ in 0..2 {
+        advance(&mut f, DAY);
+        take_tranche(&mut f).unwrap();
+    }
+
+    assert!(f.is_closed(&f.escrow(SEED)));
+    assert!(f.is_closed(&f.vault(SEED)));
+    assert_eq!(maker_balance_b(&f), RECEIVE);
+    assert_eq!(f.token_balance(&f.taker_ata_b), 0);
+
+    let registry = f
+        .ctx
+        .svm
+        .get_account(&f.registry(&f.maker.pubkey()))
+        .unwrap();
+    assert_eq!(registry.data.len(), MakerRegistry::space(0));
+    let registry = MakerRegistry::try_deserialize(&mut registry.data.as_slice()).unwrap();
+    assert!(registry.escrows.is_empty());
+}
+
+#[test]
+fn test_every_tranche_adds_volume_but_only_the_last_fills() {
+    let mut f = EscrowFixture::new();
+    make_tranched(&mut f, daily(None));
+
+    take_tranche(&mut f).unwrap();
+    assert_eq!(
+        f.maker_stats_state(&f.maker.pubkey()).counts,
+        counts(1, 0, 0)
+    );
+    let pair = f.pair_stats_state();
+    assert_eq!(pair.counts, counts(1, 0, 0));
+    assert_eq!(
+        (pair.volume_a, pair.volume_b),
+        ((AMOUNT / 4).into(), (RECEIVE / 4).into())
+    );
+
+    for _ in 0..3 {
+        advance(&mut f, DAY);
+        take_tranche(&mut f).unwrap();
+    }
+
+    assert_eq!(
+        f.maker_stats_state(&f.maker.pubkey()).counts,
+        counts(1, 1, 0)
+    );
+    let pair = f.pair_stats_state();
+    assert_eq!(pair.counts, counts(1, 1, 0));
+    assert_eq!(
+        (pair.volume_a, pair.volume_b),
+        (AMOUNT.into(), RECEIVE.into())
+    );
+    assert_eq!(
+        (pair.last_fill_a, pair.last_fill_b),
+        (AMOUNT / 4, RECEIVE / 4)
+    );
+}
+
+#[test]
+fn test_tranches_can_carry_their_own_price() {
+    let mut f = EscrowFixture::new();
+    make_tranched(&mut f, daily(Some(RECEIVE / 10)));
+
+    take_tranche(&mut f).unwrap();
+    advance(&mut f, DAY);
+    take_tranche(&mut f).unwrap();
+
+    assert_eq!(maker_balance_b(&f), RECEIVE / 5);
+    assert_eq!(f.token_balance(&f.vault(SEED)), AMOUNT / 2);
+}
+
+#[test]
+fn test_max_receive_guards_against_a_repriced_tranche() {
+    let mut f = EscrowFixture::new();
+    make_tranched(&mut f, daily(None));
+
+    // The maker doubles the tranche price ahead of the taker's fill
+    let ix = set_tranches_ix(&f, Some(daily(Some(RECEIVE / 2))));
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+
+    assert_escrow_error(
+        take_tranche_with_limit(&mut f, RECEIVE / 4),
+        EscrowError::ReceiveAboveLimit,
+    );
+    take_tranche_with_limit(&mut f, RECEIVE / 2).unwrap();
+    assert_eq!(maker_balance_b(&f), RECEIVE / 2);
+}
+
+#[test]
+fn test_tranched_escrow_only_fills_through_take_tranche() {
+    let mut f = EscrowFixture::new();
+    make_tranched(&mut f, daily(None));
+    assert_escrow_error(f.take(SEED), EscrowError::TranchedEscrow);
+
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    assert_escrow_error(take_tranche(&mut f), EscrowError::NotTranched);
+}
+
+#[test]
+fn test_tranches_are_fixed_after_first_fill() {
+    let mut f = EscrowFixture::new();
+    make_tranched(&mut f, daily(None));
+    take_tranche(&mut f).unwrap();
+
+    let ix = set_tranches_ix(&f, None);
+    let maker = f.maker.insecure_clone();
+    assert_escrow_error(f.send(&[ix], &[&maker]), EscrowError::TranchesStarted);
+}
+
+#[test]
+fn test_pause_blocks_take_tranche() {
+    let mut f = EscrowFixture::new();
+    make_tranched(&mut f, daily(None));
+    f.pause();
+
+    assert_escrow_error(take_tranche(&mut f), EscrowError::ProgramPaused);
+}
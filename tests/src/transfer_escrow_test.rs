@@ -0,0 +1,114 @@
+use crate::common::{assert_escrow_error, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use anchor_escrow::{errors::EscrowError, state::MakerRegistry};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_litesvm::TestHelpers;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+fn transfer_escrow_ix(f: &EscrowFixture, maker: &Pubkey, new_maker: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::TransferEscrow {
+            maker: *maker,
+            new_maker: *new_maker,
+            escrow: f.escrow(SEED),
+            registry: f.registry(maker),
+            new_registry: f.registry(new_maker),
+            new_maker_stats: f.maker_stats(new_maker),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::TransferEscrow {}.data(),
+    }
+}
+
+#[test]
+fn test_new_owner_receives_take_proceeds() {
+    let mut f = EscrowFixture::new();
+    let new_maker = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    f.init_registry(&new_maker.pubkey()).unwrap();
+    f.make(SEED).unwrap();
+
+    let ix = transfer_escrow_ix(&f, &f.maker.pubkey(), &new_maker.pubkey());
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+
+    let escrow = f.escrow_state(SEED);
+    assert_eq!(escrow.maker, new_maker.pubkey());
+    assert_eq!(escrow.creator, f.maker.pubkey());
+
+    let ix = f.take_ix(SEED, &f.taker.pubkey(), &new_maker.pubkey());
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker]).unwrap();
+
+    let new_maker_ata_b = get_associated_token_address(&new_maker.pubkey(), &f.mint_b.pubkey());
+    assert_eq!(f.token_balance(&new_maker_ata_b), RECEIVE);
+    assert!(f.is_closed(&f.escrow(SEED)));
+}
+
+#[test]
+fn test_new_owner_can_refund() {
+    let mut f = EscrowFixture::new();
+    let new_maker = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    f.init_registry(&new_maker.pubkey()).unwrap();
+    f.make(SEED).unwrap();
+
+    let ix = transfer_escrow_ix(&f, &f.maker.pubkey(), &new_maker.pubkey());
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+
+    // The original maker lost the right to refund
+    let ix = f.refund_ix(SEED, &f.maker.pubkey());
+    assert_escrow_error(f.send(&[ix], &[&maker]), EscrowError::InvalidMaker);
+
+    let ix = f.refund_ix(SEED, &new_maker.pubkey());
+    f.send(&[ix], &[&new_maker]).unwrap();
+
+    let new_maker_ata_a = get_associated_token_address(&new_maker.pubkey(), &f.mint_a.pubkey());
+    assert_eq!(f.token_balance(&new_maker_ata_a), AMOUNT);
+}
+
+#[test]
+fn test_only_maker_can_transfer() {
+    let mut f = EscrowFixture::new();
+    let stranger = Keypair::new();
+    f.init_registry(&stranger.pubkey()).unwrap();
+    f.make(SEED).unwrap();
+
+    let ix = transfer_escrow_ix(&f, &f.taker.pubkey(), &stranger.pubkey());
+    let taker = f.taker.insecure_clone();
+    assert_escrow_error(f.send(&[ix], &[&taker]), EscrowError::InvalidMaker);
+
+    let ix = transfer_escrow_ix(&f, &f.maker.pubkey(), &f.maker.pubkey());
+    let maker = f.maker.insecure_clone();
+    assert_escrow_error(f.send(&[ix], &[&maker]), EscrowError::InvalidMaker);
+}
+
+#[test]
+fn test_transfer_creates_new_maker_registry() {
+    let mut f = EscrowFixture::new();
+    let new_maker = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    f.make(SEED).unwrap();
+
+    let ix = transfer_escrow_ix(&f, &f.maker.pubkey(), &new_maker.pubkey());
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+
+    let account = f
+        .ctx
+        .svm
+        .get_account(&f.registry(&new_maker.pubkey()))
+        .unwrap();
+    let registry = MakerRegistry::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(registry.maker, new_maker.pubkey());
+    assert_eq!(registry.escrows, vec![f.escrow(SEED)]);
+
+    // The stats created alongside it let the escrow be taken
+    let ix = f.take_ix(SEED, &f.taker.pubkey(), &new_maker.pubkey());
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker]).unwrap();
+}
@@ -0,0 +1,234 @@
+use crate::common::{assert_escrow_error, take_instruction, EscrowFixture, AMOUNT, RECEIVE, SEED};
+use crate::mock_oracle::set_unix_timestamp;
+use anchor_escrow::{errors::EscrowError, state::Vesting, vesting::VestingSchedule};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_litesvm::TestHelpers;
+use litesvm::types::TransactionResult;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Signer};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+
+const SCHEDULE: VestingSchedule = VestingSchedule {
+    cliff: 100,
+    duration: 1_000,
+};
+
+fn set_vesting_ix(f: &EscrowFixture, schedule: Option<VestingSchedule>) -> Instruction {
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::SetVesting {
+            maker: f.maker.pubkey(),
+            escrow: f.escrow(SEED),
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::SetVesting { schedule }.data(),
+    }
+}
+
+fn make_vested(f: &mut EscrowFixture, schedule: VestingSchedule) -> TransactionResult {
+    let make_ix = f.make_ix(SEED, RECEIVE, AMOUNT);
+    let set_ix = set_vesting_ix(f, Some(schedule));
+    let maker = f.maker.insecure_clone();
+    f.send(&[make_ix, set_ix], &[&maker])
+}
+
+fn vesting(f: &EscrowFixture) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"vesting",
+            f.escrow(SEED).as_ref(),
+            f.taker.pubkey().as_ref(),
+        ],
+        &anchor_escrow::ID,
+    )
+    .0
+}
+
+fn vesting_vault(f: &EscrowFixture) -> Pubkey {
+    get_associated_token_address(&vesting(f), &f.mint_a.pubkey())
+}
+
+fn taker_ata_a(f: &EscrowFixture) -> Pubkey {
+    get_associated_token_address(&f.taker.pubkey(), &f.mint_a.pubkey())
+}
+
+fn vesting_state(f: &EscrowFixture) -> Vesting {
+    let account = f.ctx.svm.get_account(&vesting(f)).unwrap();
+    Vesting::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn take_vested(f: &mut EscrowFixture) -> TransactionResult {
+    let mut accounts = f.take_accounts(SEED, &f.taker.pubkey(), &f.maker.pubkey());
+    accounts.vesting = Some(vesting(f));
+    accounts.vesting_vault = Some(vesting_vault(f));
+    let ix = take_instruction(accounts, vec![]);
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+fn claim(f: &mut EscrowFixture) -> TransactionResult {
+    let ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::Claim {
+            beneficiary: f.taker.pubkey(),
+            vesting: vesting(f),
+            mint: f.mint_a.pubkey(),
+            vesting_vault: vesting_vault(f),
+            beneficiary_ata: taker_ata_a(f),
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::Claim {}.data(),
+    };
+    let taker = f.taker.insecure_clone();
+    f.send(&[ix], &[&taker])
+}
+
+#[test]
+fn test_take_moves_proceeds_into_vesting() {
+    let mut f = EscrowFixture::new();
+    make_vested(&mut f, SCHEDULE).unwrap();
+    assert_eq!(f.escrow_state(SEED).vesting(), Some(SCHEDULE));
+
+    take_vested(&mut f).unwrap();
+
+    assert!(f.is_closed(&f.escrow(SEED)));
+    assert_eq!(f.token_balance(&vesting_vault(&f)), AMOUNT);
+    assert_eq!(f.token_balance(&taker_ata_a(&f)), 0);
+
+    let state = vesting_state(&f);
+    assert_eq!(state.beneficiary, f.taker.pubkey());
+    assert_eq!(state.schedule, SCHEDULE);
+    assert_eq!(state.total, AMOUNT);
+    assert_eq!(state.claimed, 0);
+}
+
+#[test]
+fn test_take_uses_a_precreated_vesting_vault() {
+    let mut f = EscrowFixture::new();
+    make_vested(&mut f, SCHEDULE).unwrap();
+
+    // Created and topped up by someone else before the take
+    let stranger = f.ctx.create_funded_account(1_000_000_000).unwrap();
+    let ix = create_associated_token_account_idempotent(
+        &stranger.pubkey(),
+        &vesting(&f),
+        &f.mint_a.pubkey(),
+        &spl_token::id(),
+    );
+    f.send(&[ix], &[&stranger]).unwrap();
+    let ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &f.mint_a.pubkey(),
+        &vesting_vault(&f),
+        &f.maker.pubkey(),
+        &[],
+        1,
+    )
+    .unwrap();
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+
+    take_vested(&mut f).unwrap();
+    let state = vesting_state(&f);
+    assert_eq!(state.total, AMOUNT + 1);
+
+    // The extra token vests too, so the final claim empties and closes the vault
+    set_unix_timestamp(&mut f.ctx.svm, state.start + SCHEDULE.duration);
+    claim(&mut f).unwrap();
+    assert_eq!(f.token_balance(&taker_ata_a(&f)), AMOUNT + 1);
+    assert!(f.is_closed(&vesting_vault(&f)));
+}
+
+#[test]
+fn test_final_claim_sweeps_tokens_sent_after_the_take() {
+    let mut f = EscrowFixture::new();
+    make_vested(&mut f, SCHEDULE).unwrap();
+    take_vested(&mut f).unwrap();
+
+    let ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &f.mint_a.pubkey(),
+        &vesting_vault(&f),
+        &f.maker.pubkey(),
+        &[],
+        1,
+    )
+    .unwrap();
+    let maker = f.maker.insecure_clone();
+    f.send(&[ix], &[&maker]).unwrap();
+
+    let state = vesting_state(&f);
+    assert_eq!(state.total, AMOUNT);
+    set_unix_timestamp(&mut f.ctx.svm, state.start + SCHEDULE.duration);
+    claim(&mut f).unwrap();
+    assert_eq!(f.token_balance(&taker_ata_a(&f)), AMOUNT + 1);
+    assert!(f.is_closed(&vesting_vault(&f)));
+    assert!(f.is_closed(&vesting(&f)));
+}
+
+#[test]
+fn test_claim_releases_linearly_after_cliff() {
+    let mut f = EscrowFixture::new();
+    make_vested(&mut f, SCHEDULE).unwrap();
+    take_vested(&mut f).unwrap();
+    let start = vesting_state(&f).start;
+
+    set_unix_timestamp(&mut f.ctx.svm, start + SCHEDULE.cliff - 1);
+    assert_escrow_error(claim(&mut f), EscrowError::NothingToClaim);
+
+    set_unix_timestamp(&mut f.ctx.svm, start + SCHEDULE.cliff);
+    claim(&mut f).unwrap();
+    assert_eq!(f.token_balance(&taker_ata_a(&f)), AMOUNT / 10);
+
+    set_unix_timestamp(&mut f.ctx.svm, start + 550);
+    claim(&mut f).unwrap();
+    assert_eq!(f.token_balance(&taker_ata_a(&f)), AMOUNT * 55 / 100);
+    assert_eq!(vesting_state(&f).claimed, AMOUNT * 55 / 100);
+    assert_escrow_error(claim(&mut f), EscrowError::NothingToClaim);
+
+    set_unix_timestamp(&mut f.ctx.svm, start + SCHEDULE.duration);
+    claim(&mut f).unwrap();
+    assert_eq!(f.token_balance(&taker_ata_a(&f)), AMOUNT);
+    assert!(f.is_closed(&vesting(&f)));
+    assert!(f.is_closed(&vesting_vault(&f)));
+}
+
+#[test]
+fn test_take_requires_vesting_accounts_exactly_when_vested() {
+    let mut f = EscrowFixture::new();
+    make_vested(&mut f, SCHEDULE).unwrap();
+    assert_escrow_error(f.take(SEED), EscrowError::VestingAccountMismatch);
+
+    let mut f = EscrowFixture::new();
+    f.make(SEED).unwrap();
+    assert_escrow_error(take_vested(&mut f), EscrowError::VestingAccountMismatch);
+}
+
+#[test]
+fn test_set_vesting_rejects_invalid_schedule() {
+    let mut f = EscrowFixture::new();
+
+    for schedule in [
+        VestingSchedule {
+            cliff: 0,
+            duration: 0,
+        },
+        VestingSchedule {
+            cliff: 1_001,
+            duration: 1_000,
+        },
+        VestingSchedule {
+            cliff: -1,
+            duration: 1_000,
+        },
+    ] {
+        assert_escrow_error(
+            make_vested(&mut f, schedule),
+            EscrowError::InvalidVestingSchedule,
+        );
+    }
+}